@@ -0,0 +1,19 @@
+use slope::run;
+
+fn main() {
+    run!("
+        fn adder(a) = x => x + a;
+        let add2 = adder(2);
+        add2(10);
+        { f(10) for f in { adder(1), adder(2), adder(3) } };
+        let curry = f => a => b => f(a, b);
+        curry((x, y) -> x - y)(10)(3);
+        let y = 1;
+        fn get_y() = y;
+        fn shadow(y) = get_y();
+        shadow(5);
+        fn even(n) = { true if n == 0; odd(n - 1) else; };
+        fn odd(n) = { false if n == 0; even(n - 1) else; };
+        even(10);
+    ");
+}
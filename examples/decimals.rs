@@ -0,0 +1,11 @@
+use slope::run;
+
+fn main() {
+    run!(decimal "
+        0.1 + 0.2 == 0.3;
+        19.99 * 3;
+        10.0 / 3.0;
+        1.05 ^ 10;
+        2.0 ^ 0.5;
+    ");
+}
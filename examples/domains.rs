@@ -0,0 +1,17 @@
+use slope::run;
+
+fn main() {
+    run!("
+        fn sqrt(x: R) -> R = x ^ 0.5;
+        sqrt(4);
+        sqrt(true);
+        fn choose(n in N, k in { 0, ..., n }) = n! / (k! * (n - k)!);
+        choose(5, 2);
+        choose(5, 7);
+        fn pred(n in N) -> N = n - 1;
+        pred(1);
+        pred;
+        let double = (x in Z) => 2 * x;
+        double(1/2);
+    ");
+}
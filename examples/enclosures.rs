@@ -0,0 +1,17 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let g = within(9.81, 0.01);
+        let t = within(2, 0.05);
+        let fall = g * t^2 / 2;
+        fall;
+        bounds(fall);
+        enclosure(1, 2) * enclosure(-3, 4);
+        enclosure(-2, 3)^2;
+        1 / enclosure(-1, 1);
+        enclosure(1, 2) < enclosure(3, 4);
+        enclosure(1, 2) < enclosure(1.5, 4);
+        within(0.1, 0) + within(0.2, 0);
+    ");
+}
@@ -0,0 +1,16 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let Suits = { hearts, spades, clubs, diamonds };
+        Suits;
+        spades in Suits;
+        fn is_red(s) = {
+            true if s == hearts or s == diamonds;
+            false else;
+        };
+        { s for s in Suits } /\\ { hearts, clubs };
+        is_red(diamonds);
+        is_red(clubs);
+    ");
+}
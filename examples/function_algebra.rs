@@ -0,0 +1,17 @@
+use slope::run;
+
+fn main() {
+    run!("
+        fn f(x) = x ^ 2;
+        fn g(x) = x + 1;
+        (f + g)(3);
+        (2 * f)(3);
+        (f ∘ g)(3);
+        (g << f)(3);
+        (g ^ 5)(0);
+        (2 ^ g)(3);
+        f - g;
+        f ∘ g ^ 2;
+        (-f)(3);
+    ");
+}
@@ -0,0 +1,20 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let square = x => x ^ 2;
+        let add = (a, b) -> a + b;
+        let cube = fn(x) = x ^ 3;
+        cube(2);
+        square(4);
+        add(2, 3);
+        fn apply(f, x) = f(x);
+        apply(x => x * 10, 4);
+        let fs = { x => x ^ 2, x => x + 1, x => x ^ 2 };
+        fs;
+        { f(3) for f in fs };
+        let sign = { (x => 1) if true; (x => -1) else; };
+        sign(5);
+        poly(x => x ^ 2 - 1);
+    ");
+}
@@ -0,0 +1,17 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let g = graph({ 1, 2, 3, 4 }, { (1, 2), (2, 3) });
+        neighbours(g, 2);
+        degree(g, 2);
+        components(g);
+        let roads = graph({ 1, 2, 3, 4 }, { (1, 2, 1), (2, 4, 5), (1, 3, 2), (3, 4, 1) });
+        shortest_path(roads, 1, 4);
+        bfs(roads, 1);
+        dfs(roads, 1);
+        let tasks = digraph({ 1, 2, 3 }, { (1, 2), (2, 3) });
+        reachable(tasks, 3, 1);
+        has_cycle(tasks);
+    ");
+}
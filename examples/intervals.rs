@@ -0,0 +1,15 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let unit = [0, 1);
+        0 in unit;
+        1 in unit;
+        1 / 3 in (0, 1];
+        unit \\/ [1, 2);
+        [0, 3) \\ (1, 2];
+        [0 .. 2] /\\ { 1, 5 };
+        \\ unit;
+        unit <= [0 .. 1];
+    ");
+}
@@ -0,0 +1,12 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let m = [[2, 1], [1, 3]];
+        let identity = [ { 1 if i == j; 0 else; } where i in { 1, 2 }, j in { 1, 2 } ];
+        m * inverse(m) == identity;
+        det(m);
+        transpose([1, 2, 3]);
+        [1, 2, 3] * [4, 5, 6];
+    ");
+}
@@ -0,0 +1,15 @@
+use slope::run;
+
+fn main() {
+    run!("
+        (3 mod 7) * 5;
+        (3 mod 7) / 2;
+        (3 mod 8) / 2;
+        (2 mod 1000003) ^ 1000000;
+        38 ≡ 3 (mod 7);
+        2^10 ≡ 1024 (mod 7);
+        inverse(3 mod 7);
+        crt(2 mod 3, 3 mod 5, 2 mod 7);
+        dlog(3 mod 7, 6);
+    ");
+}
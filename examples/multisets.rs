@@ -0,0 +1,16 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let rolls = {: 3, 5, 3, 6, 3 :};
+        |rolls|;
+        count(rolls, 3);
+        sum(rolls) / |rolls|;
+        let a = {: 1, 1, 2 :};
+        let b = {: 1, 2, 2, 3 :};
+        a \\/ b;
+        a /\\ b;
+        a + b;
+        a \\ b;
+    ");
+}
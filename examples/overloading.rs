@@ -0,0 +1,19 @@
+use slope::run;
+
+fn main() {
+    run!("
+        fn truncate(n: Z) = n;
+        fn truncate(x: R) = x - x % 1;
+        truncate(7);
+        truncate(-2.5);
+        truncate;
+        fn size(x) = |x|;
+        fn size(x, y) = (x ^ 2 + y ^ 2) ^ 0.5;
+        size(-3);
+        size(3, 4);
+        fn sign(x) = 0;
+        fn sign(x in { y in R: y > 0 }) = 1;
+        sign(5);
+        sign(-5);
+    ");
+}
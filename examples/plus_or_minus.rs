@@ -4,7 +4,7 @@ fn main() {
     // for x^2 - 4x - 12
     run!("
         fn quadratic_formula(a, b, c) = {
-            undefined if a == 0 or b ^ 2 - 4 * a * c < 0;
+            undefined if a == 0;
             - b / (2 * a) +/- (b ^ 2 - 4 * a * c) ^ 0.5 / (2 * a) else;
         };
 
@@ -0,0 +1,17 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let p = poly(fn(x) = x^2 - 4x - 12);
+        let q = poly([1, 2]);
+        p * q;
+        p / q;
+        divide(poly([1, 2, 3]), poly([2, 1]));
+        derivative(p);
+        degree(p * q);
+        gcd(p, q);
+        roots(p);
+        roots(poly([1, 0, -2]));
+        p(6);
+    ");
+}
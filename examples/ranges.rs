@@ -0,0 +1,13 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let odds = { 1, 3, ..., 99 };
+        odds;
+        7 in odds;
+        8 in odds;
+        |odds|;
+        sum({ 1, ..., 1000000000 });
+        { 10, 9, ..., 1 } /\\ { 0, 5, 10, 15 };
+    ");
+}
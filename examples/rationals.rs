@@ -0,0 +1,11 @@
+use slope::run;
+
+fn main() {
+    run!("
+        1 / 3 + 1 / 3 + 1 / 3 == 1;
+        2 / 6;
+        (2 / 3) ^ -2;
+        { 1 / 2, 2 / 4, 1 / 3 };
+        1 / 2 + 0.5;
+    ");
+}
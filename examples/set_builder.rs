@@ -0,0 +1,12 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let digits = { 0, 1, 2, 3, 4, 5, 6, 7, 8, 9 };
+        let evens = { i in digits: i % 2 == 0 };
+        6 in evens;
+        7 in evens;
+        |evens|;
+        evens /\\ { i in digits: i > 5 };
+    ");
+}
@@ -0,0 +1,10 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let a = { 1, 2, 3 };
+        { i ^ 2 for i in a };
+        { x + y for x in a, y in { 10, 20 } };
+        { (x, y) for x in a, y in { j in a: j > x } };
+    ");
+}
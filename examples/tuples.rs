@@ -0,0 +1,10 @@
+use slope::run;
+
+fn main() {
+    run!("
+        let p = (3, 4);
+        fn norm(v) = (v[0] ^ 2 + v[1] ^ 2) ^ 0.5;
+        norm(p);
+        { (2, 1), (1, 2), (1, 2) };
+    ");
+}
@@ -0,0 +1,18 @@
+use slope::run;
+
+fn main() {
+    run!("
+        import units;
+        let weight = 70 kg * 9.81 m/s^2;
+        weight;
+        weight as kN;
+        72 km/h as m/s;
+        5 km + 300 m;
+        (3 m) / (1 km);
+        1 km == 1000 m;
+        { 1 km, 1000 m, 2 m };
+        1 / 2 s;
+        g0 * 2 s;
+        c * 1 day as km;
+    ");
+}
@@ -0,0 +1,179 @@
+use super::environment::Environment;
+use super::errors::RuntimeError;
+use super::expression::Expression;
+use super::object::Object;
+use super::types::Type;
+use crate::interpreter::token::Token;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, self};
+use std::hash::{Hash, Hasher};
+
+// a function built from others: `f + g` and `2 * f` apply the operator to the results,
+// `f ∘ g` applies g and then f, and `f ^ n` applies f n times
+#[derive(Debug, Clone)]
+pub struct Composite {
+    pub operator: Token,  // an arithmetic operator, Compose, or Exponent with a count on the right
+    pub left: Box<Object>,
+    pub right: Box<Object>,
+}
+
+impl Composite {
+    fn function(operator: Token, left: Object, right: Object) -> Object {
+        Object::Composite(Composite { operator, left: Box::new(left), right: Box::new(right) })
+    }
+
+    fn iterates(&self) -> bool {
+        self.operator == Token::Exponent && matches!(*self.right, Object::Integer(_))
+    }
+
+    pub fn call(&self, arguments: &[Object]) -> Result<Object, RuntimeError> {
+        match (&self.operator, &*self.right) {
+            (Token::Exponent, Object::Integer(times)) => {
+                let mut value = match arguments {
+                    [argument] => argument.clone(),
+                    _ => return Err(RuntimeError::OperatorError(format!("Expected 1 argument to {} got {}.", self, arguments.len()))),
+                };
+                for _ in 0..*times {
+                    if let Object::Undefined = value {
+                        break
+                    };
+                    value = Environment::call(&self.left, &[value])?;
+                }
+                Ok(value)
+            },
+            (Token::Compose, inner) => match Environment::call(inner, arguments)? {
+                Object::Undefined => Ok(Object::Undefined),
+                value => Environment::call(&self.left, &[value]),
+            },
+            (operator, _) => {
+                let result = |side: &Object| if side.is_function() { Environment::call(side, arguments) } else { Ok(side.clone()) };
+                let (left, right) = (result(&self.left)?, result(&self.right)?);
+                match operator {
+                    Token::Plus => left + right,
+                    Token::Minus => left - right,
+                    Token::Multiply => left * right,
+                    Token::Division => left / right,
+                    Token::Exponent => left.pow(&right),
+                    t => Err(RuntimeError::OperatorError(format!("Cannot use `{}` on functions.", t))),
+                }
+            },
+        }
+    }
+
+    // the type of a function taking as many parameters as the functions it was built from
+    pub fn kind(&self) -> Type {
+        match (&self.operator, &*self.left) {
+            _ if self.iterates() => Type::Function(1),
+            (Token::Compose, _) => Type::of(&self.right),
+            (_, left) if left.is_function() => Type::of(left),
+            _ => Type::of(&self.right),
+        }
+    }
+}
+
+// whether the operands of an arithmetic operator make a function, being functions or numbers
+pub fn operands(left: &Object, right: &Object) -> bool {
+    (left.is_function() || right.is_function())
+        && (left.is_function() || left.is_number())
+        && (right.is_function() || right.is_number())
+}
+
+// the number of parameters, when it is known
+fn arity(function: &Object) -> Option<usize> {
+    match Type::of(function) {
+        Type::Function(arity) => Some(arity),
+        _ => None,
+    }
+}
+
+// both functions are given the same arguments, so they have to take as many
+pub fn pointwise(operator: Token, left: Object, right: Object) -> Result<Object, RuntimeError> {
+    match (arity(&left), arity(&right)) {
+        (Some(arity), Some(arity2)) if arity != arity2 => Err(RuntimeError::TypeError(format!(
+            "Cannot combine {} and {} (they take {} and {} parameters).", left, right, arity, arity2
+        ))),
+        _ => Ok(Composite::function(operator, left, right)),
+    }
+}
+
+// `f ∘ g`, where f is given the single result of g
+pub fn compose(left: &Object, right: &Object) -> Result<Object, RuntimeError> {
+    if !left.is_function() || !right.is_function() {
+        return Err(RuntimeError::OperatorError(format!("Cannot compose {} and {} (expected functions).", left, right)))
+    };
+    match arity(left) {
+        Some(arity) if arity != 1 => Err(RuntimeError::TypeError(format!("Cannot compose {} and {} ({} takes {} parameters, not 1).", left, right, left, arity))),
+        _ => Ok(Composite::function(Token::Compose, left.clone(), right.clone())),
+    }
+}
+
+// `f ^ n` for a natural number n composes f with itself, while `f ^ g` and `2 ^ f` are pointwise
+pub fn pow(base: &Object, exponent: &Object) -> Result<Object, RuntimeError> {
+    match (base, exponent) {
+        (function, Object::Integer(times)) if function.is_function() && *times >= 0 => match arity(function) {
+            Some(arity) if arity != 1 => Err(RuntimeError::TypeError(format!("Cannot raise {} to {} (it takes {} parameters, not 1).", function, times, arity))),
+            _ => Ok(Composite::function(Token::Exponent, function.clone(), exponent.clone())),
+        },
+        (base, exponent) if exponent.is_function() && operands(base, exponent) => pointwise(Token::Exponent, base.clone(), exponent.clone()),
+        _ => Err(RuntimeError::OperatorError(format!("Cannot raise {} to {} (expected a natural number of compositions).", base, exponent))),
+    }
+}
+
+// functions are written as arrow functions, in parentheses so the operators around them stay clear
+fn operand(value: &Object) -> String {
+    match value {
+        Object::Function { parameters, expression, .. } => format!("({})", Expression::FunctionLiteral {
+            parameters: parameters.clone(),
+            body: Box::new(expression.clone()),
+        }),
+        function if function.is_function() => format!("({})", function),
+        value => value.to_string(),
+    }
+}
+
+impl Display for Composite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", operand(&self.left), self.operator, operand(&self.right))
+    }
+}
+
+impl PartialEq for Composite {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Composite {}
+
+impl Hash for Composite {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.operator.to_string().hash(state);
+        self.left.hash(state);
+        self.right.hash(state);
+    }
+}
+
+impl PartialOrd for Composite {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// functions come before numbers, which may not be comparable with each other
+fn compare(left: &Object, right: &Object) -> Ordering {
+    match (left.is_function(), right.is_function()) {
+        (true, true) => left.cmp(right),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => left.partial_cmp(right).unwrap_or_else(|| left.to_string().cmp(&right.to_string())),
+    }
+}
+
+// tokens have no order of their own, so operators are ordered by how they are written
+impl Ord for Composite {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.operator.to_string().cmp(&other.operator.to_string())
+            .then_with(|| compare(&self.left, &other.left))
+            .then_with(|| compare(&self.right, &other.right))
+    }
+}
@@ -0,0 +1,218 @@
+use super::object::Object;
+use crate::interpreter::token::Token;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, self};
+use std::hash::{Hash, Hasher};
+
+// a real number known only to lie between two bounds; every operation rounds
+// the bounds outward, so the true result always stays inside
+#[derive(Debug, Clone, Copy)]
+pub struct Enclosure {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+// the floats just below and above the exact result that was rounded to `value`,
+// where `error` has the sign of the exact result minus `value`
+fn directed(value: f64, error: f64) -> (f64, f64) {
+    if error.is_nan() {
+        (value.next_down(), value.next_up())
+    } else if error > 0.0 {
+        (value, value.next_up())
+    } else if error < 0.0 {
+        (value.next_down(), value)
+    } else {
+        (value, value)
+    }
+}
+
+// the rounding errors of sums, products and quotients can be computed exactly
+fn sum(a: f64, b: f64) -> (f64, f64) {
+    let total = a + b;
+    let b_part = total - a;
+    directed(total, (a - (total - b_part)) + (b - b_part))
+}
+
+fn product(a: f64, b: f64) -> (f64, f64) {
+    let value = a * b;
+    directed(value, a.mul_add(b, -value))
+}
+
+fn quotient(a: f64, b: f64) -> (f64, f64) {
+    let value = a / b;
+    directed(value, (-value).mul_add(b, a) * b.signum())
+}
+
+impl Enclosure {
+    fn exactly(value: f64) -> Self {
+        Enclosure { lo: value, hi: value }
+    }
+
+    // numbers that a float cannot hold exactly are widened to the floats on either side
+    pub fn of(value: &Object) -> Option<Self> {
+        match value {
+            Object::Enclosure(enclosure) => Some(*enclosure),
+            Object::Real(value) => Some(Enclosure::exactly(*value)),
+            Object::Integer(integer) if *integer as f64 as i128 == *integer as i128 => Some(Enclosure::exactly(*integer as f64)),
+            number if number.is_number() => {
+                let value = number.as_real()?;
+                Some(Enclosure { lo: value.next_down(), hi: value.next_up() })
+            },
+            _ => None,
+        }
+    }
+
+    pub fn contains_zero(&self) -> bool {
+        self.lo <= 0.0 && 0.0 <= self.hi
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Enclosure { lo: sum(self.lo, other.lo).0, hi: sum(self.hi, other.hi).1 }
+    }
+
+    pub fn neg(&self) -> Self {
+        Enclosure { lo: -self.hi, hi: -self.lo }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    // the extremes are always among the combinations of the bounds
+    fn combine(&self, other: &Self, operation: fn(f64, f64) -> (f64, f64)) -> Self {
+        let bounds = [(self.lo, other.lo), (self.lo, other.hi), (self.hi, other.lo), (self.hi, other.hi)]
+            .iter()
+            .map(|(a, b)| operation(*a, *b))
+            .collect::<Vec<_>>();
+        Enclosure {
+            lo: bounds.iter().map(|bound| bound.0).fold(f64::INFINITY, f64::min),
+            hi: bounds.iter().map(|bound| bound.1).fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        self.combine(other, product)
+    }
+
+    // None when the divisor could be zero
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        if other.contains_zero() {
+            None
+        } else {
+            Some(self.combine(other, quotient))
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.lo >= 0.0 {
+            *self
+        } else if self.hi <= 0.0 {
+            self.neg()
+        } else {
+            Enclosure { lo: 0.0, hi: self.hi.max(-self.lo) }
+        }
+    }
+
+    // None when a negative power would divide by zero
+    pub fn pow(&self, exponent: i64) -> Option<Self> {
+        if exponent < 0 {
+            return Enclosure::exactly(1.0).div(&self.pow(exponent.checked_neg()?)?)
+        };
+        // even powers only depend on the magnitude, and on the result the power is increasing
+        let base = if exponent % 2 == 0 { self.abs() } else { *self };
+        Some(Enclosure {
+            lo: Enclosure::exactly(base.lo).power_by_squaring(exponent).lo,
+            hi: Enclosure::exactly(base.hi).power_by_squaring(exponent).hi,
+        })
+    }
+
+    fn power_by_squaring(&self, mut exponent: i64) -> Self {
+        let (mut result, mut base) = (Enclosure::exactly(1.0), *self);
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(&base);
+            };
+            base = base.mul(&base);
+            exponent /= 2;
+        }
+        result
+    }
+
+    // Some when every pair of values from the two enclosures agrees, otherwise None
+    fn less(&self, other: &Self, strict: bool) -> Option<bool> {
+        match strict {
+            true if self.hi < other.lo => Some(true),
+            true if self.lo >= other.hi => Some(false),
+            false if self.hi <= other.lo => Some(true),
+            false if self.lo > other.hi => Some(false),
+            _ => None,
+        }
+    }
+
+    fn equals(&self, other: &Self) -> Option<bool> {
+        if self.lo == self.hi && other.lo == other.hi && self.lo == other.lo {
+            Some(true)
+        } else if self.hi < other.lo || other.hi < self.lo {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+// whether both sides can take part in enclosure arithmetic, with at least one of them an enclosure
+pub fn operands(left: &Object, right: &Object) -> Option<(Enclosure, Enclosure)> {
+    match (left, right) {
+        (Object::Enclosure(_), _) | (_, Object::Enclosure(_)) => Some((Enclosure::of(left)?, Enclosure::of(right)?)),
+        _ => None,
+    }
+}
+
+// comparisons involving an enclosure are true, false or undefined when the enclosures overlap
+pub fn compare(left: &Object, right: &Object, token: &Token) -> Option<Object> {
+    let (left, right) = operands(left, right)?;
+    let result = match token {
+        Token::LessThan => left.less(&right, true),
+        Token::LessThanEquals => left.less(&right, false),
+        Token::GreaterThan => right.less(&left, true),
+        Token::GreaterThanEquals => right.less(&left, false),
+        Token::Equals => left.equals(&right),
+        Token::NotEquals => left.equals(&right).map(|equal| !equal),
+        _ => return None,
+    };
+    Some(result.map_or(Object::Undefined, Object::Boolean))
+}
+
+impl PartialEq for Enclosure {
+    fn eq(&self, other: &Self) -> bool {
+        self.lo == other.lo && self.hi == other.hi
+    }
+}
+
+impl Eq for Enclosure {}
+
+impl Hash for Enclosure {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // adding zero makes -0.0 hash like 0.0, which it equals
+        (self.lo + 0.0).to_bits().hash(state);
+        (self.hi + 0.0).to_bits().hash(state);
+    }
+}
+
+impl PartialOrd for Enclosure {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Enclosure {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.lo + 0.0).total_cmp(&(other.lo + 0.0)).then((self.hi + 0.0).total_cmp(&(other.hi + 0.0)))
+    }
+}
+
+impl Display for Enclosure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "⟨{}, {}⟩", self.lo, self.hi)
+    }
+}
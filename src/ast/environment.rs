@@ -1,44 +1,95 @@
-use std::mem;
 use super::errors::RuntimeError;
 use super::expression::Expression;
 use super::location::Location;
 use super::operator::Operator;
+use super::parameter::Parameter;
 use super::statement::Statement;
 use crate::interpreter::token::Token;
-use std::collections::{HashMap, BTreeSet};
-use super::object::Object;
-use super::modules::{Module, math_constants_builtins, set_builtins};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, BTreeMap, BTreeSet};
+use std::fmt::{Debug, Formatter, self};
+use std::rc::Rc;
+use super::object::{Object, Condition};
+use super::generator::Generator;
+use super::interval::{self, Interval};
+use super::types::Type;
+use super::units;
+use super::enclosure;
+use super::residue;
+use super::composite;
+use super::modules::{Module, math_constants_builtins, set_builtins, linear_algebra_builtins, number_set_builtins, graph_builtins, polynomial_builtins, units_builtins, units_clash, enclosure_builtins, residue_builtins};
 
-#[derive(Debug, Clone)]
+// the environment for a function body with its parameters bound, and the domains they were found in
+type Binding = (Environment, Vec<Option<Object>>);
+
+// how real literals (and constants like PI) are represented
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericMode {
+    Float,    // binary floating point (the default)
+    Decimal,  // base-10 decimals, so `0.1 + 0.2 == 0.3`
+}
+
+// clones share their bindings, so a function that captured its environment
+// also sees names declared after it, such as its own
+#[derive(Clone)]
 pub struct Environment {
-    bindings: HashMap<String, Object>,
-    parent: Option<Box<Environment>>
+    bindings: Rc<RefCell<HashMap<String, Object>>>,
+    // names bound by `fn`, which later declarations may overload
+    declared: Rc<RefCell<HashSet<String>>>,
+    parent: Option<Box<Environment>>,
+    mode: NumericMode,
 }
 
 impl Environment {
     pub fn new() -> Self {
+        Self::with_mode(NumericMode::Float)
+    }
+
+    pub fn with_mode(mode: NumericMode) -> Self {
         let mut env = Self {
-            bindings: HashMap::new(),
-            parent: None
+            bindings: Rc::new(RefCell::new(HashMap::new())),
+            declared: Rc::new(RefCell::new(HashSet::new())),
+            parent: None,
+            mode,
         };
         env.import(set_builtins);
         env.import(math_constants_builtins);
+        env.import(linear_algebra_builtins);
+        env.import(number_set_builtins);
+        env.import(graph_builtins);
+        env.import(polynomial_builtins);
+        env.import(enclosure_builtins);
+        env.import(residue_builtins);
         env
     }
 
+    pub fn mode(&self) -> NumericMode {
+        self.mode
+    }
+
     pub fn import(&mut self, func: Module) {
         func(self)
     }
 
+    // builtins are found through the parent, so they can be shadowed (by parameters, say)
     pub fn new_child(&self) -> Self {
-        let mut child = Self::new();
-        child.parent = Some(Box::new(self.clone()));
-        child
+        Self {
+            bindings: Rc::new(RefCell::new(HashMap::new())),
+            declared: Rc::new(RefCell::new(HashSet::new())),
+            parent: Some(Box::new(self.clone())),
+            mode: self.mode,
+        }
+    }
+
+    // the same for every clone of an environment, and different for every new one
+    pub fn id(&self) -> usize {
+        Rc::as_ptr(&self.bindings) as usize
     }
 
     pub fn get(&self, key: &str) -> Option<Object> {
-        match self.bindings.get(key) {
-            Some(value) => Some(value.clone()),
+        let value = self.bindings.borrow().get(key).cloned();
+        match value {
+            Some(value) => Some(value),
             None => {
                 match &self.parent {
                     Some(env) => env.get(key),
@@ -49,7 +100,8 @@ impl Environment {
     }
 
     pub fn set(&mut self, key: &String, value: &Object) -> Result<(), RuntimeError> {
-        match self.bindings.insert(key.clone(), value.clone()) {
+        let previous = self.bindings.borrow_mut().insert(key.clone(), value.clone());
+        match previous {
             Some(_) => Err(RuntimeError::NameError(format!("Cannot re-declare value `{}`.", key))),
             None => Ok(())
         }
@@ -61,24 +113,278 @@ impl Environment {
                 expression,
                 identifier,
             } => {
-                self.set(identifier, &self.eval(expression)?)?;
+                let value = self.eval(expression)?;
+                // the members of a newly declared enumeration can be referred to by name
+                if let (Expression::Enumeration(_), Object::Set { items, .. }) = (expression, &value) {
+                    for atom in items {
+                        match atom {
+                            Object::Atom { name, .. } if self.get(name).is_none() => self.set(name, atom)?,
+                            _ => (),
+                        };
+                    }
+                };
+                self.set(identifier, &value)?;
                 Ok(Object::Undefined)
             },
             Statement::ExpressionStatement { expression } => self.eval(expression),
             Statement::FunctionDeclaration {
                 identifier,
                 parameters,
+                codomain,
                 expression,
             } => {
-                self.set(identifier, &Object::Function {
+                let function = Object::Function {
                     parameters: parameters.clone().to_vec(),
-                    expression: expression.clone()
-                })?;
+                    codomain: codomain.clone(),
+                    expression: expression.clone(),
+                    scope: Box::new(self.clone()),
+                };
+                self.declare(identifier, function)?;
+                Ok(Object::Undefined)
+            },
+            Statement::Import { module } => {
+                match module.as_str() {
+                    "units" => match units_clash(self) {
+                        Some(name) => return Err(RuntimeError::NameError(format!("Cannot import units: `{}` is already bound.", name))),
+                        None => self.import(units_builtins),
+                    },
+                    _ => return Err(RuntimeError::NameError(format!("Unknown module `{}`.", module))),
+                };
                 Ok(Object::Undefined)
             },
         }
     }
     
+    // the members of a generator's set, in order
+    fn generator_values(&self, generator: &Generator) -> Result<Vec<Object>, RuntimeError> {
+        match self.eval(&generator.set)?.materialise()? {
+            Object::Set { items, .. } => Ok(items.into_iter().collect()),
+            obj => Err(RuntimeError::TypeError(format!("Expected a set to draw `{}` from, got {}.", generator.variable, obj)))
+        }
+    }
+
+    // whether `value` is a member of the set `set` evaluates to
+    fn belongs(&self, value: &Object, set: &Expression) -> Result<bool, RuntimeError> {
+        Ok(matches!(value.in_(&self.eval(set)?)?, Object::Boolean(true)))
+    }
+
+    // a function declared again under the same name, with other domains or another number of
+    // parameters, is an overload of the first (functions bound by `let` can't be overloaded)
+    fn declare(&mut self, key: &String, function: Object) -> Result<(), RuntimeError> {
+        let existing = self.bindings.borrow().get(key).cloned();
+        let mut definitions = match existing {
+            Some(definition @ Object::Function { .. }) if self.declared.borrow().contains(key) => vec![definition],
+            Some(Object::Overloads(definitions)) if self.declared.borrow().contains(key) => definitions,
+            _ => {
+                self.set(key, &function)?;
+                self.declared.borrow_mut().insert(key.clone());
+                return Ok(())
+            },
+        };
+        let signature = |definition: &Object| match definition {
+            Object::Function { parameters, .. } => parameters.iter().map(|parameter| format!("{:?}", parameter.domain)).collect::<Vec<_>>(),
+            _ => vec![],
+        };
+        if definitions.iter().any(|definition| signature(definition) == signature(&function)) {
+            return Err(RuntimeError::NameError(format!("Cannot re-declare function `{}` with the same parameter domains.", key)))
+        };
+        definitions.push(function);
+        self.bindings.borrow_mut().insert(key.clone(), Object::Overloads(definitions));
+        Ok(())
+    }
+
+    // a builtin registered again under the same name with another number of parameters is an overload of the first
+    pub fn overload(&mut self, key: &String, function: &Object) -> Result<(), RuntimeError> {
+        let existing = self.bindings.borrow().get(key).cloned();
+        let mut definitions = match existing {
+            Some(definition @ Object::BuiltinFunction { .. }) => vec![definition],
+            Some(Object::Overloads(definitions)) if !self.declared.borrow().contains(key) => definitions,
+            _ => return self.set(key, function),
+        };
+        if definitions.iter().any(|definition| Type::of(definition) == Type::of(function)) {
+            return Err(RuntimeError::NameError(format!("Cannot re-declare builtin `{}` with the same number of parameters.", key)))
+        };
+        definitions.push(function.clone());
+        self.bindings.borrow_mut().insert(key.clone(), Object::Overloads(definitions));
+        Ok(())
+    }
+
+    // the result of calling a function on arguments that have already been evaluated
+    pub fn call(function: &Object, arguments: &[Object]) -> Result<Object, RuntimeError> {
+        match function {
+            Object::Function { parameters, codomain, expression, scope } => match Self::bind(parameters, scope, arguments)? {
+                Some((env, _)) => env.apply(codomain, expression),
+                // outside the domain, as with `1 / 0`, the function is undefined
+                None => Ok(Object::Undefined),
+            },
+            Object::Overloads(definitions) => Self::dispatch(function, definitions, arguments),
+            Object::Composite(composite) => composite.call(arguments),
+            Object::BuiltinFunction { body, .. } => body(arguments.to_vec()),
+            Object::Polynomial(polynomial) => match arguments {
+                [argument] => polynomial.evaluate(argument),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to {} got {}.", polynomial, arguments.len())))
+            },
+            func => Err(RuntimeError::OperatorError(format!("Illegal call expression `{}`.", func)))
+        }
+    }
+
+    // None when an argument is outside its domain
+    fn bind(parameters: &[Parameter], scope: &Environment, arguments: &[Object]) -> Result<Option<Binding>, RuntimeError> {
+        // the body sees the parameters and the names where the function was defined
+        let mut env = scope.new_child();
+        let mut domains = vec![];
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            // a domain can depend on the parameters before it
+            let domain = match &parameter.domain {
+                Some(domain) => {
+                    let domain = env.eval(domain)?;
+                    if !matches!(argument.in_(&domain)?, Object::Boolean(true)) {
+                        return Ok(None)
+                    };
+                    Some(domain)
+                },
+                None => None,
+            };
+            env.set(&parameter.name, argument)?;
+            domains.push(domain);
+        }
+        Ok(Some((env, domains)))
+    }
+
+    // the body of a function whose parameters are bound in this environment
+    fn apply(&self, codomain: &Option<Expression>, expression: &Expression) -> Result<Object, RuntimeError> {
+        let result = self.eval(expression)?;
+        match codomain {
+            Some(codomain) if !matches!(result, Object::Undefined) && !self.belongs(&result, codomain)? => {
+                Err(RuntimeError::TypeError(format!("Expected a result in {} got {}.", codomain, result)))
+            },
+            _ => Ok(result),
+        }
+    }
+
+    // calls the definition whose domains hold the arguments and are each within the matching domain
+    // of every other such definition, where a parameter without a domain accepts anything
+    fn dispatch(function: &Object, definitions: &[Object], arguments: &[Object]) -> Result<Object, RuntimeError> {
+        // builtins are told apart by their number of parameters alone
+        let builtin = definitions.iter().find(|definition| matches!(definition, Object::BuiltinFunction { parameters, .. } if parameters.len() == arguments.len()));
+        if let Some(Object::BuiltinFunction { body, .. }) = builtin {
+            return body(arguments.to_vec())
+        };
+        let mut candidates = vec![];
+        let mut arity_matches = false;
+        for definition in definitions {
+            if let Object::Function { parameters, codomain, expression, scope } = definition {
+                if parameters.len() != arguments.len() {
+                    continue
+                };
+                arity_matches = true;
+                if let Some((env, domains)) = Self::bind(parameters, scope, arguments)? {
+                    candidates.push((definition, env, domains, codomain, expression));
+                };
+            };
+        }
+        if !arity_matches {
+            return Err(RuntimeError::OperatorError(format!("No definition of {} takes {} arguments.", function, arguments.len())))
+        };
+        let within = |domains: &[Option<Object>], others: &[Option<Object>]| domains.iter().zip(others).all(|pair| match pair {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(domain), Some(other)) => matches!(domain.is_subset(other), Ok(Object::Boolean(true))),
+        });
+        let most_specific = candidates.iter().enumerate().filter(|(i, (_, _, domains, ..))| {
+            candidates.iter().enumerate().all(|(j, (_, _, others, ..))| i == &j || within(domains, others))
+        }).map(|(_, candidate)| candidate).collect::<Vec<_>>();
+        match most_specific.as_slice() {
+            [(_, env, _, codomain, expression)] => env.apply(codomain, expression),
+            [] if candidates.is_empty() => Ok(Object::Undefined),
+            _ => Err(RuntimeError::TypeError(format!(
+                "Ambiguous call with arguments ({}): none of {} is more specific than the others.",
+                arguments.iter().map(Object::to_string).collect::<Vec<_>>().join(", "),
+                candidates.iter().map(|(definition, ..)| definition.to_string()).collect::<Vec<_>>().join("; "),
+            ))),
+        }
+    }
+
+    // whether `value` meets every condition when bound to `variable`
+    pub fn satisfies(&self, variable: &String, value: &Object, conditions: &[Condition]) -> Result<bool, RuntimeError> {
+        let mut env = self.new_child();
+        env.set(variable, value)?;
+        for condition in conditions {
+            match env.eval(&condition.expression)? {
+                Object::Boolean(true) => (),
+                Object::Boolean(false) => return Ok(false),
+                obj => return Err(RuntimeError::TypeError(format!("Set builder conditions should result in a boolean, got `{}`.", obj)))
+            }
+        }
+        Ok(true)
+    }
+
+    // evaluate `expression` once for every value drawn by `generator`
+    fn build_entries(&self, expression: &Expression, generator: &Generator) -> Result<Vec<Object>, RuntimeError> {
+        self.generator_values(generator)?.iter().map(|value| {
+            let mut env = self.new_child();
+            env.set(&generator.variable, value)?;
+            env.eval(expression)
+        }).collect()
+    }
+
+    // evaluate `expression` for every combination of values drawn by the generators
+    fn comprehend(&self, expression: &Expression, generators: &[Generator], members: &mut Vec<Object>) -> Result<(), RuntimeError> {
+        match generators.split_first() {
+            Some((generator, rest)) => {
+                for value in self.generator_values(generator)? {
+                    let mut env = self.new_child();
+                    env.set(&generator.variable, &value)?;
+                    env.comprehend(expression, rest, members)?;
+                }
+                Ok(())
+            },
+            None => {
+                members.push(self.eval(expression)?);
+                Ok(())
+            }
+        }
+    }
+
+    // members of a set or multiset must all be defined and of the same type
+    fn members_kind(members: &[Object], collection: &str) -> Result<Type, RuntimeError> {
+        let mut kind = Type::Unknown;
+        for obj in members {
+            if obj.is_undefined() {
+                return Err(RuntimeError::TypeError(format!("Cannot put undefined in a {}.", collection.to_lowercase())))
+            };
+            kind = match kind.join(&Type::of(obj)) {
+                Some(kind) => kind,
+                None => return Err(RuntimeError::TypeError(format!(
+                    "{} literal members must all be the same type, got {} and {}.", collection, kind, Type::of(obj)
+                ))),
+            };
+        }
+        Ok(kind)
+    }
+
+    fn collect_set(members: Vec<Object>) -> Result<Object, RuntimeError> {
+        let kind = Self::members_kind(&members, "Set")?;
+        let mut items = BTreeSet::new();
+        items.extend(members);
+        Ok(Object::Set {
+            items,
+            kind
+        })
+    }
+
+    fn collect_multiset(members: Vec<Object>) -> Result<Object, RuntimeError> {
+        let kind = Self::members_kind(&members, "Multiset")?;
+        let mut items = BTreeMap::new();
+        for obj in members {
+            *items.entry(obj).or_insert(0) += 1;
+        }
+        Ok(Object::Multiset {
+            items,
+            kind
+        })
+    }
+
     pub fn eval(&self, expr: &Expression) -> Result<Object, RuntimeError> {
         use Expression::*;
         match expr {
@@ -90,8 +396,18 @@ impl Environment {
                 }
             },
             IntegerLiteral(value) => Ok(Object::Integer(*value)), // value,
-            RealLiteral(value) => Ok(Object::Real(*value)),       // value,
+            BigIntegerLiteral(value) => Ok(Object::integer(value.clone())),
+            RealLiteral(value) => match self.mode {
+                NumericMode::Float => Ok(Object::Real(value.parse().unwrap())),
+                NumericMode::Decimal => Ok(Object::decimal_literal(value)),
+            },
             BooleanLiteral(value) => Ok(Object::Boolean(*value)), // value,
+            FunctionLiteral { parameters, body } => Ok(Object::Function {
+                parameters: parameters.clone(),
+                codomain: None,
+                expression: *body.clone(),
+                scope: Box::new(self.clone()),
+            }),
             UndefinedLiteral => Ok(Object::Undefined),
             Combination {
                 operator: Operator(token, Location::Infix),
@@ -102,28 +418,37 @@ impl Environment {
                 Token::Multiply => self.eval(left)? * self.eval(right)?,
                 Token::Minus => self.eval(left)? - self.eval(right)?,
                 Token::Division => self.eval(left)? / self.eval(right)?,
-                Token::NotEquals => Ok(Object::Boolean(self.eval(left)? != self.eval(right)?)),
-                Token::Equals => Ok(Object::Boolean(self.eval(left)? == self.eval(right)?)),
-                Token::GreaterThan => Ok(Object::Boolean(self.eval(left)? > self.eval(right)?)),
-                Token::GreaterThanEquals => Ok(Object::Boolean(self.eval(left)? >= self.eval(right)?)),
-                Token::LessThan => {
-                    let left = self.eval(left)?;
-                    let right = self.eval(right)?;
-                    match (left, right) {
-                        (s1 @ Object::Set { .. }, s2 @ Object::Set { .. }) => {
-                            s1.is_proper_subset(&s2)
-                        },
-                        (obj1, obj2) => Ok(Object::Boolean(obj1 < obj2))
-                    }
-                },
-                Token::LessThanEquals => {
+                Token::NotEquals
+                | Token::Equals
+                | Token::GreaterThan
+                | Token::GreaterThanEquals
+                | Token::LessThan
+                | Token::LessThanEquals => {
                     let left = self.eval(left)?;
                     let right = self.eval(right)?;
-                    match (left, right) {
-                        (s1 @ Object::Set { .. }, s2 @ Object::Set { .. }) => {
-                            s1.is_subset(&s2)
+                    // comparisons with enclosures may be undecided
+                    if let Some(result) = enclosure::compare(&left, &right, token) {
+                        return Ok(result)
+                    };
+                    match (token, left, right) {
+                        (Token::LessThan, s1, s2) if s1.is_set() && s2.is_set() => s1.is_proper_subset(&s2),
+                        (Token::LessThanEquals, s1, s2) if s1.is_set() && s2.is_set() => s1.is_subset(&s2),
+                        (Token::GreaterThan, s1, s2) if s1.is_set() && s2.is_set() => s2.is_proper_subset(&s1),
+                        (Token::GreaterThanEquals, s1, s2) if s1.is_set() && s2.is_set() => s2.is_subset(&s1),
+                        (token, obj1, obj2) => {
+                            match token {
+                                Token::NotEquals | Token::Equals => obj1.comparable(&obj2)?,
+                                _ => obj1.orderable(&obj2)?,
+                            };
+                            Ok(Object::Boolean(match token {
+                                Token::NotEquals => obj1 != obj2,
+                                Token::Equals => obj1 == obj2,
+                                Token::GreaterThan => obj1 > obj2,
+                                Token::GreaterThanEquals => obj1 >= obj2,
+                                Token::LessThan => obj1 < obj2,
+                                _ => obj1 <= obj2,
+                            }))
                         },
-                        (obj1, obj2) => Ok(Object::Boolean(obj1 <= obj2))
                     }
                 },
                 Token::Exponent => self.eval(left)?.pow(&self.eval(right)?),
@@ -138,6 +463,10 @@ impl Environment {
                 Token::SetDifference => self.eval(left)?.set_difference(&self.eval(right)?),
                 Token::SymmetricDifference => self.eval(left)?.set_symmetric_difference(&self.eval(right)?),
                 Token::Intersection => self.eval(left)?.set_intersection(&self.eval(right)?),
+                Token::As => units::convert(&self.eval(left)?, &self.eval(right)?),
+                Token::Mod => residue::reduce(&self.eval(left)?, &self.eval(right)?),
+                Token::Congruent => residue::congruent(&self.eval(left)?, &self.eval(right)?),
+                Token::Compose => composite::compose(&self.eval(left)?, &self.eval(right)?),
                 t => Err(RuntimeError::OperatorError(format!("Cannot use `{}` as an infix operator.", t))),
             },
             Combination {
@@ -153,6 +482,7 @@ impl Environment {
                     Ok(obj) => !obj,
                     Err(e) => Err(e)
                 },
+                Token::SetDifference => self.eval(right)?.complement(),
                 t => Err(RuntimeError::OperatorError(format!("Cannot use `{}` as a prefix operator.", t))),
             },
             Combination {
@@ -161,28 +491,16 @@ impl Environment {
                 right: None,
             } => match token {
                 Token::Bang => self.eval(left)?.factorial(),
+                Token::Imaginary => self.eval(left)?.imaginary(),
                 t => Err(RuntimeError::OperatorError(format!("Cannot use `{}` as a postfix operator.", t))),
             },
             Call {
                 function,
                 arguments,
             } => {
-                // create new environment with current one as its parent
-                let mut env = self.new_child();
-                
-                // get parameters from current definition
-                match self.eval(function)? {
-                    Object::Function { parameters, expression } => {
-                        for (p, v) in parameters.iter().zip(arguments.iter()) {
-                            env.set(&p.name, &self.eval(v)?)?;
-                        };
-                        env.eval(&expression)
-                    },
-                    Object::BuiltinFunction { body, .. } => {
-                        body(arguments.iter().map(|expr| self.eval(expr).unwrap()).collect())
-                    },
-                    func => Err(RuntimeError::OperatorError(format!("Illegal call expression `{}`.", func)))
-                }
+                let function = self.eval(function)?;
+                let arguments = arguments.iter().map(|argument| self.eval(argument)).collect::<Result<Vec<_>, _>>()?;
+                Self::call(&function, &arguments)
             },
             PiecewiseBlock(arms) => {
                 let mut arm_iter = arms.iter();
@@ -208,28 +526,97 @@ impl Environment {
             },
             AbsoluteValue(expr) => self.eval(expr)?.abs(),
             SetLiteral(expressions) => {
-                let mut items = BTreeSet::new();
-                let mut kind = None;
-                for expr in expressions {
-                    let obj = self.eval(expr)?;
-                    if obj.is_undefined() {
-                        return Err(RuntimeError::TypeError("Cannot put undefined in a set.".into()))
-                    };
-                    if kind.is_none() {
-                        kind = Some(mem::discriminant(&obj));
-                    } else if let Some(disc) = kind {
-                        if mem::discriminant(&obj) != disc {
-                            return Err(RuntimeError::TypeError("Set literal members must all be the same type.".into()))
-                        }
-                    }
-                    items.insert(obj);
-                }
-                Ok(Object::Set {
-                    items,
-                    kind
+                Self::collect_set(expressions.iter().map(|expr| self.eval(expr)).collect::<Result<_, _>>()?)
+            },
+            // if every name is bound this is an ordinary set of values, and if only some are it redeclares them
+            Enumeration(names) if names.iter().all(|name| self.get(name).is_none()) => {
+                if let Some(name) = names.iter().enumerate().find_map(|(index, name)| names[..index].contains(name).then_some(name)) {
+                    return Err(RuntimeError::NameError(format!("Enumeration member `{}` is declared twice.", name)))
+                };
+                Self::collect_set(names.iter().enumerate().map(|(index, name)| Object::Atom { name: name.clone(), index }).collect())
+            },
+            Enumeration(names) => {
+                if names.iter().any(|name| self.get(name).is_none()) {
+                    let name = names.iter().find(|name| self.get(name).is_some()).unwrap();
+                    return Err(RuntimeError::NameError(format!("Cannot re-declare value `{}`.", name)))
+                };
+                Self::collect_set(names.iter().map(|name| self.eval(&Identifier(name.clone()))).collect::<Result<_, _>>()?)
+            },
+            MultisetLiteral(expressions) => {
+                Self::collect_multiset(expressions.iter().map(|expr| self.eval(expr)).collect::<Result<_, _>>()?)
+            },
+            IntervalLiteral { start, end, start_closed, end_closed } => {
+                let interval = Interval::new(self.eval(start)?, self.eval(end)?, *start_closed, *end_closed)?;
+                Ok(interval::to_object(interval::normalise(vec![interval])))
+            },
+            RangeLiteral { first, second, last } => {
+                let integer = |expr: &Expression| match self.eval(expr)? {
+                    Object::Integer(value) => Ok(value),
+                    obj => Err(RuntimeError::TypeError(format!("Range members must be integers, got {}.", obj)))
+                };
+                let first = integer(first)?;
+                let step = match second {
+                    Some(second) => integer(second)?.checked_sub(first)
+                        .ok_or_else(|| RuntimeError::OperatorError("Range step is too large.".into()))?,
+                    None => 1,
+                };
+                if step == 0 {
+                    return Err(RuntimeError::OperatorError("Range members must be distinct.".into()))
+                };
+                Ok(Object::range(first, integer(last)?, step))
+            },
+            SetComprehension { expression, generators } => {
+                let mut members = vec![];
+                self.comprehend(expression, generators, &mut members)?;
+                Self::collect_set(members)
+            },
+            SetBuilder { variable, parent_set, conditions } => {
+                let parent_set = self.eval(parent_set)?;
+                if !parent_set.is_set() {
+                    return Err(RuntimeError::TypeError(format!("Expected a set to draw `{}` from, got {}.", variable, parent_set)))
+                };
+                Ok(Object::SetBuilder {
+                    variable: variable.clone(),
+                    parent_set: Box::new(parent_set),
+                    conditions: conditions.iter().map(|expression| Condition { expression: expression.clone() }).collect(),
+                    scope: Box::new(self.clone()),
                 })
             },
+            TupleLiteral(expressions) => Ok(Object::Tuple {
+                items: expressions.iter().map(|expr| self.eval(expr)).collect::<Result<_, _>>()?
+            }),
+            Index { collection, index } => self.eval(collection)?.index(&self.eval(index)?),
+            VectorLiteral(expressions) => Ok(Object::Vector {
+                items: expressions.iter().map(|expr| self.eval(expr)).collect::<Result<_, _>>()?
+            }),
+            MatrixLiteral(rows) => Ok(Object::Matrix {
+                rows: rows.iter().map(|row| row.iter().map(|expr| self.eval(expr)).collect()).collect::<Result<_, _>>()?
+            }),
+            MatrixBuilder { expression, generators } => match generators.as_slice() {
+                [generator] => Ok(Object::Vector { items: self.build_entries(expression, generator)? }),
+                [row_generator, column_generator] => Ok(Object::Matrix {
+                    rows: self.generator_values(row_generator)?.iter().map(|value| {
+                        let mut env = self.new_child();
+                        env.set(&row_generator.variable, value)?;
+                        env.build_entries(expression, column_generator)
+                    }).collect::<Result<_, _>>()?
+                }),
+                _ => Err(RuntimeError::OperatorError("Matrix builders take one or two generators.".into())),
+            },
             Combination { .. } => panic!("Illegal expression."),
         }
     }
 }
+
+// bindings can hold functions that captured this environment, so only the names are shown
+impl Debug for Environment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut names = self.bindings.borrow().keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        f.debug_struct("Environment")
+            .field("names", &names)
+            .field("parent", &self.parent)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
@@ -1,14 +1,17 @@
 use std::fmt::{Display, Formatter, self};
 use super::operator::Operator;
+use super::generator::Generator;
+use super::parameter::Parameter;
 use std::hash::{Hash, Hasher};
-use rust_decimal::prelude::*;
+use num_bigint::BigInt;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expression {
     Identifier(String),
     IntegerLiteral(i64),
+    BigIntegerLiteral(BigInt),
     BooleanLiteral(bool),
-    RealLiteral(f64),
+    RealLiteral(String),
     UndefinedLiteral,
     Combination {
         left: Option<Box<Expression>>,
@@ -22,15 +25,49 @@ pub enum Expression {
     PiecewiseBlock(Vec<(Expression, Expression)>),
     AbsoluteValue(Box<Expression>),
     SetLiteral(Vec<Expression>),
+    Enumeration(Vec<String>),  // a set literal of bare names
+    MultisetLiteral(Vec<Expression>),
+    SetBuilder {
+        variable: String,
+        parent_set: Box<Expression>,
+        conditions: Vec<Expression>
+    },
+    IntervalLiteral {
+        start: Box<Expression>,
+        end: Box<Expression>,
+        start_closed: bool,
+        end_closed: bool,
+    },
+    RangeLiteral {
+        first: Box<Expression>,
+        second: Option<Box<Expression>>,  // sets the step; the step is 1 without it
+        last: Box<Expression>
+    },
+    SetComprehension {
+        expression: Box<Expression>,
+        generators: Vec<Generator>
+    },
+    TupleLiteral(Vec<Expression>),
+    Index {
+        collection: Box<Expression>,
+        index: Box<Expression>
+    },
+    VectorLiteral(Vec<Expression>),
+    MatrixLiteral(Vec<Vec<Expression>>),
+    MatrixBuilder {
+        expression: Box<Expression>,
+        generators: Vec<Generator>  // one generator builds a vector, two build a matrix
+    },
+    FunctionLiteral {
+        parameters: Vec<Parameter>,
+        body: Box<Expression>,
+    },
 }
 
 impl Hash for Expression {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        use Expression::*;
-        match self {
-            RealLiteral(value) => Decimal::from_f64(*value).unwrap().hash(state),
-            rest => rest.hash(state),
-        }
+        // the debug form spells out the whole structure, so equal expressions hash alike
+        format!("{:?}", self).hash(state)
     }
 }
 
@@ -40,14 +77,16 @@ impl Display for Expression {
         match self {
             Identifier(name) => write!(f, "{}", name),
             IntegerLiteral(value) => write!(f, "{}", value),
+            BigIntegerLiteral(value) => write!(f, "{}", value),
             BooleanLiteral(value) => write!(f, "{}", value),
             RealLiteral(value) => write!(f, "{}", value),
             UndefinedLiteral => write!(f, "undefined"),
             Combination { left, operator, right } => {
-                if let Some(_value) = left {
-                    write!(f, "{} {} {}", left.as_ref().unwrap(), operator, right.as_ref().unwrap())
-                } else {
-                    write!(f, "{} {}", operator, right.as_ref().unwrap())
+                match (left, right) {
+                    (Some(left), Some(right)) => write!(f, "{} {} {}", left, operator, right),
+                    (Some(left), None) => write!(f, "{}{}", left, operator),
+                    (None, Some(right)) => write!(f, "{} {}", operator, right),
+                    (None, None) => write!(f, "{}", operator),
                 }
             },
             Call { function, arguments } => {
@@ -77,7 +116,90 @@ impl Display for Expression {
                     acc.push_str(&member.to_string());
                     acc
                 }))
-            }
+            },
+            Enumeration(names) => write!(f, "{{ {} }}", names.join(", ")),
+            MultisetLiteral(expressions) => {
+                write!(f, "{{: {} :}}", expressions.iter().fold(String::new(), |mut acc, member| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&member.to_string());
+                    acc
+                }))
+            },
+            SetBuilder { variable, parent_set, conditions } => {
+                write!(f, "{{ {} in {}: {} }}", variable, parent_set, conditions.iter().fold(String::new(), |mut acc, condition| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&condition.to_string());
+                    acc
+                }))
+            },
+            IntervalLiteral { start, end, start_closed, end_closed } => write!(
+                f,
+                "{}{} .. {}{}",
+                if *start_closed { "[" } else { "(" },
+                start,
+                end,
+                if *end_closed { "]" } else { ")" }
+            ),
+            RangeLiteral { first, second: Some(second), last } => write!(f, "{{ {}, {}, ..., {} }}", first, second, last),
+            RangeLiteral { first, second: None, last } => write!(f, "{{ {}, ..., {} }}", first, last),
+            SetComprehension { expression, generators } => {
+                write!(f, "{{ {} for {} }}", expression, generators.iter().fold(String::new(), |mut acc, generator| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&generator.to_string());
+                    acc
+                }))
+            },
+            TupleLiteral(expressions) if expressions.len() == 1 => write!(f, "({},)", expressions[0]),
+            TupleLiteral(expressions) => {
+                write!(f, "({})", expressions.iter().fold(String::new(), |mut acc, member| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&member.to_string());
+                    acc
+                }))
+            },
+            Index { collection, index } => write!(f, "{}[{}]", collection, index),
+            VectorLiteral(expressions) => {
+                write!(f, "[{}]", expressions.iter().fold(String::new(), |mut acc, member| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&member.to_string());
+                    acc
+                }))
+            },
+            MatrixLiteral(rows) => {
+                write!(f, "[{}]", rows.iter().fold(String::new(), |mut acc, row| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&VectorLiteral(row.clone()).to_string());
+                    acc
+                }))
+            },
+            FunctionLiteral { parameters, body } if parameters.len() == 1 && parameters[0].domain.is_none() => write!(f, "{} => {}", parameters[0], body),
+            FunctionLiteral { parameters, body } => write!(
+                f,
+                "({}) => {}",
+                parameters.iter().map(Parameter::to_string).collect::<Vec<_>>().join(", "),
+                body
+            ),
+            MatrixBuilder { expression, generators } => {
+                write!(f, "[ {} where {} ]", expression, generators.iter().fold(String::new(), |mut acc, generator| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&generator.to_string());
+                    acc
+                }))
+            },
         }
     }
 }
\ No newline at end of file
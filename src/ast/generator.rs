@@ -0,0 +1,16 @@
+use std::fmt::{Display, Formatter, self};
+use std::hash::Hash;
+use super::expression::Expression;
+
+// `variable in set`, as used by builders to draw values from a set
+#[derive(Debug, PartialEq, Clone, Hash)]
+pub struct Generator {
+    pub variable: String,
+    pub set: Expression,
+}
+
+impl Display for Generator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {}", self.variable, self.set)
+    }
+}
@@ -0,0 +1,206 @@
+use super::errors::RuntimeError;
+use super::object::Object;
+use super::types::Type;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::cmp::Ordering;
+
+// every node, with each of its neighbours and the weight of the edge to it; undirected edges are stored both ways
+pub type Adjacency = BTreeMap<Object, BTreeMap<Object, Object>>;
+
+fn members(set: &Object, context: &str) -> Result<BTreeSet<Object>, RuntimeError> {
+    match set.materialise()? {
+        Object::Set { items, .. } => Ok(items),
+        obj => Err(RuntimeError::TypeError(format!("Expected a set of {} got {}.", context, obj))),
+    }
+}
+
+// nodes of a different type are never in the graph, and comparing against them would fail
+fn contains(adjacency: &Adjacency, node: &Object) -> bool {
+    adjacency.keys().next().is_some_and(|first| Type::of(first).join(&Type::of(node)).is_some()) && adjacency.contains_key(node)
+}
+
+fn edges_from<'a>(adjacency: &'a Adjacency, node: &Object) -> Result<&'a BTreeMap<Object, Object>, RuntimeError> {
+    if contains(adjacency, node) {
+        Ok(&adjacency[node])
+    } else {
+        Err(RuntimeError::TypeError(format!("{} is not a node of the graph.", node)))
+    }
+}
+
+// edges are (u, v) pairs, or (u, v, w) triples for an edge of weight w
+pub fn build(nodes: &Object, edges: &Object, directed: bool) -> Result<Object, RuntimeError> {
+    let mut adjacency = Adjacency::new();
+    for node in members(nodes, "nodes")? {
+        adjacency.insert(node, BTreeMap::new());
+    }
+    for edge in members(edges, "edges")? {
+        let (from, to, weight) = match &edge {
+            Object::Tuple { items } => match items.as_slice() {
+                [from, to] => (from, to, Object::Integer(1)),
+                [from, to, weight] if weight.as_real().is_some() => (from, to, weight.clone()),
+                _ => return Err(RuntimeError::TypeError(format!("Expected an edge (u, v) or (u, v, weight) got {}.", edge))),
+            },
+            obj => return Err(RuntimeError::TypeError(format!("Expected an edge (u, v) or (u, v, weight) got {}.", obj))),
+        };
+        if !contains(&adjacency, from) || !contains(&adjacency, to) {
+            return Err(RuntimeError::TypeError(format!("Edge {} joins a node that is not in the graph.", edge)))
+        };
+        if !directed {
+            adjacency.get_mut(to).unwrap().insert(from.clone(), weight.clone());
+        };
+        adjacency.get_mut(from).unwrap().insert(to.clone(), weight);
+    }
+    Ok(Object::Graph { adjacency, directed })
+}
+
+// each undirected edge is listed once, from the smaller node
+pub fn edges(adjacency: &Adjacency, directed: bool) -> Vec<Object> {
+    adjacency.iter().flat_map(|(from, neighbours)| neighbours.iter().filter_map(move |(to, weight)| {
+        if !directed && to < from {
+            None
+        } else if weight == &Object::Integer(1) {
+            Some(Object::Tuple { items: vec![from.clone(), to.clone()] })
+        } else {
+            Some(Object::Tuple { items: vec![from.clone(), to.clone(), weight.clone()] })
+        }
+    })).collect()
+}
+
+pub fn neighbours(adjacency: &Adjacency, node: &Object) -> Result<Object, RuntimeError> {
+    let mut items = BTreeSet::new();
+    items.extend(edges_from(adjacency, node)?.keys().cloned());
+    Ok(Object::set(items))
+}
+
+// in a directed graph this counts edges in both directions; a loop counts twice
+pub fn degree(adjacency: &Adjacency, directed: bool, node: &Object) -> Result<Object, RuntimeError> {
+    let outgoing = edges_from(adjacency, node)?.len();
+    let incoming = if directed {
+        adjacency.values().filter(|neighbours| neighbours.contains_key(node)).count()
+    } else {
+        usize::from(adjacency[node].contains_key(node))
+    };
+    Ok(Object::Integer((outgoing + incoming) as i64))
+}
+
+// the nodes reachable from start, in the order they are visited; neighbours are visited smallest first
+pub fn traverse(adjacency: &Adjacency, start: &Object, breadth_first: bool) -> Result<Vec<Object>, RuntimeError> {
+    edges_from(adjacency, start)?;
+    let mut visited = BTreeSet::new();
+    let mut order = vec![];
+    let mut pending = VecDeque::from(vec![start.clone()]);
+    while let Some(node) = if breadth_first { pending.pop_front() } else { pending.pop_back() } {
+        if !visited.insert(node.clone()) {
+            continue
+        };
+        let unvisited = adjacency[&node].keys().filter(|neighbour| !visited.contains(*neighbour)).cloned();
+        if breadth_first {
+            pending.extend(unvisited);
+        } else {
+            pending.extend(unvisited.rev());
+        };
+        order.push(node);
+    }
+    Ok(order)
+}
+
+// dijkstra's algorithm; the path is a tuple of nodes, or undefined when there is none
+pub fn shortest_path(adjacency: &Adjacency, from: &Object, to: &Object) -> Result<Object, RuntimeError> {
+    edges_from(adjacency, from)?;
+    edges_from(adjacency, to)?;
+    if adjacency.values().flat_map(BTreeMap::values).any(|weight| weight < &Object::Integer(0)) {
+        return Err(RuntimeError::OperatorError("Cannot find shortest paths with negative edge weights.".into()))
+    };
+    let mut distances = BTreeMap::new();
+    let mut previous = BTreeMap::new();
+    let mut done = BTreeSet::new();
+    distances.insert(from.clone(), Object::Integer(0));
+    loop {
+        let closest = distances.iter()
+            .filter(|(node, _)| !done.contains(*node))
+            .min_by(|(_, left), (_, right)| left.partial_cmp(right).unwrap_or(Ordering::Equal))
+            .map(|(node, distance)| (node.clone(), distance.clone()));
+        let (node, distance) = match closest {
+            Some(closest) => closest,
+            None => return Ok(Object::Undefined),
+        };
+        if &node == to {
+            let mut path = vec![node];
+            while let Some(step) = previous.get(path.last().unwrap()) {
+                path.push(Object::clone(step));
+            }
+            path.reverse();
+            return Ok(Object::Tuple { items: path })
+        };
+        for (neighbour, weight) in &adjacency[&node] {
+            let candidate = (distance.clone() + weight.clone())?;
+            if !done.contains(neighbour) && distances.get(neighbour).is_none_or(|known| &candidate < known) {
+                distances.insert(neighbour.clone(), candidate);
+                previous.insert(neighbour.clone(), node.clone());
+            };
+        }
+        done.insert(node);
+    }
+}
+
+// the edges of a directed graph are followed both ways, giving its weakly connected components
+pub fn components(adjacency: &Adjacency) -> Object {
+    let mut undirected = adjacency.clone();
+    for (from, neighbours) in adjacency {
+        for (to, weight) in neighbours {
+            undirected.get_mut(to).unwrap().insert(from.clone(), weight.clone());
+        }
+    }
+    let mut seen = BTreeSet::new();
+    let mut components = BTreeSet::new();
+    for node in adjacency.keys() {
+        if seen.contains(node) {
+            continue
+        };
+        let mut component = BTreeSet::new();
+        component.extend(traverse(&undirected, node, true).unwrap());
+        seen.extend(component.iter().cloned());
+        components.insert(Object::set(component));
+    }
+    Object::set(components)
+}
+
+fn directed_cycle_from(adjacency: &Adjacency, node: &Object, active: &mut BTreeSet<Object>, finished: &mut BTreeSet<Object>) -> bool {
+    if finished.contains(node) {
+        return false
+    };
+    if !active.insert(node.clone()) {
+        return true
+    };
+    let found = adjacency[node].keys().any(|neighbour| directed_cycle_from(adjacency, neighbour, active, finished));
+    active.remove(node);
+    finished.insert(node.clone());
+    found
+}
+
+fn undirected_cycle_from(adjacency: &Adjacency, node: &Object, parent: Option<&Object>, seen: &mut BTreeSet<Object>) -> bool {
+    seen.insert(node.clone());
+    adjacency[node].keys().any(|neighbour| {
+        if Some(neighbour) == parent {
+            false
+        } else if seen.contains(neighbour) {
+            true
+        } else {
+            undirected_cycle_from(adjacency, neighbour, Some(node), seen)
+        }
+    })
+}
+
+pub fn has_cycle(adjacency: &Adjacency, directed: bool) -> bool {
+    let mut seen = BTreeSet::new();
+    let mut active = BTreeSet::new();
+    adjacency.keys().any(|node| {
+        if directed {
+            directed_cycle_from(adjacency, node, &mut active, &mut seen)
+        } else {
+            // a loop is a cycle on its own
+            adjacency[node].contains_key(node)
+                || (!seen.contains(node) && undirected_cycle_from(adjacency, node, None, &mut seen))
+        }
+    })
+}
@@ -0,0 +1,204 @@
+use super::errors::RuntimeError;
+use super::object::Object;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, self};
+use std::hash::{Hash, Hasher};
+
+// an interval of the real line; infinite ends are always open
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interval {
+    pub start: Object,
+    pub end: Object,
+    pub start_closed: bool,
+    pub end_closed: bool,
+}
+
+// endpoints are always real numbers, so they can always be ordered
+fn compare(left: &Object, right: &Object) -> Ordering {
+    left.partial_cmp(right).unwrap_or(Ordering::Equal)
+}
+
+impl Interval {
+    pub fn new(start: Object, end: Object, start_closed: bool, end_closed: bool) -> Result<Self, RuntimeError> {
+        for endpoint in [&start, &end].iter() {
+            if endpoint.as_real().is_none_or(f64::is_nan) {
+                return Err(RuntimeError::TypeError(format!("Interval endpoints must be real numbers, got {}.", endpoint)))
+            };
+        }
+        Ok(Interval {
+            start_closed: start_closed && start.as_real().unwrap().is_finite(),
+            end_closed: end_closed && end.as_real().unwrap().is_finite(),
+            start,
+            end,
+        })
+    }
+
+    pub fn point(value: &Object) -> Result<Self, RuntimeError> {
+        Interval::new(value.clone(), value.clone(), true, true)
+    }
+
+    fn is_empty(&self) -> bool {
+        match compare(&self.start, &self.end) {
+            Ordering::Less => false,
+            Ordering::Equal => !(self.start_closed && self.end_closed),
+            Ordering::Greater => true,
+        }
+    }
+
+    fn is_point(&self) -> bool {
+        compare(&self.start, &self.end) == Ordering::Equal
+    }
+
+    pub fn contains(&self, value: &Object) -> bool {
+        if value.as_real().is_none() {
+            return false
+        };
+        let after_start = match compare(&self.start, value) {
+            Ordering::Less => true,
+            Ordering::Equal => self.start_closed,
+            Ordering::Greater => false,
+        };
+        let before_end = match compare(value, &self.end) {
+            Ordering::Less => true,
+            Ordering::Equal => self.end_closed,
+            Ordering::Greater => false,
+        };
+        after_start && before_end
+    }
+
+    // whether an interval starting no earlier than this one overlaps or touches it
+    fn joins(&self, later: &Interval) -> bool {
+        match compare(&later.start, &self.end) {
+            Ordering::Less => true,
+            Ordering::Equal => self.end_closed || later.start_closed,
+            Ordering::Greater => false,
+        }
+    }
+}
+
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(&self.start, &other.start)
+            .then(other.start_closed.cmp(&self.start_closed))
+            .then(compare(&self.end, &other.end))
+            .then(self.end_closed.cmp(&other.end_closed))
+    }
+}
+
+// numbers hash by value, so `[1/2 .. 1]` and `[0.5 .. 1]` hash the same as they are equal
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start.hash(state);
+        self.end.hash(state);
+        (self.start_closed, self.end_closed).hash(state);
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_point() {
+            write!(f, "{{ {} }}", self.start)
+        } else {
+            write!(
+                f,
+                "{}{} .. {}{}",
+                if self.start_closed { "[" } else { "(" },
+                self.start,
+                self.end,
+                if self.end_closed { "]" } else { ")" }
+            )
+        }
+    }
+}
+
+// sorted, with overlapping or touching intervals merged and empty ones dropped
+pub fn normalise(intervals: Vec<Interval>) -> Vec<Interval> {
+    let mut intervals = intervals.into_iter().filter(|interval| !interval.is_empty()).collect::<Vec<_>>();
+    intervals.sort();
+    let mut merged: Vec<Interval> = vec![];
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if last.joins(&interval) => match compare(&interval.end, &last.end) {
+                Ordering::Greater => {
+                    last.end = interval.end;
+                    last.end_closed = interval.end_closed;
+                },
+                Ordering::Equal => last.end_closed |= interval.end_closed,
+                Ordering::Less => (),
+            },
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+// the rest of the real line; the intervals must already be normalised
+pub fn complement(intervals: &[Interval]) -> Vec<Interval> {
+    let mut gaps = vec![];
+    let (mut start, mut start_closed) = (Object::Real(f64::NEG_INFINITY), false);
+    for interval in intervals {
+        gaps.push(Interval {
+            start,
+            end: interval.start.clone(),
+            start_closed,
+            end_closed: !interval.start_closed,
+        });
+        start = interval.end.clone();
+        start_closed = !interval.end_closed;
+    }
+    gaps.push(Interval {
+        start,
+        end: Object::Real(f64::INFINITY),
+        start_closed,
+        end_closed: false,
+    });
+    normalise(gaps)
+}
+
+pub fn union(left: &[Interval], right: &[Interval]) -> Vec<Interval> {
+    normalise(left.iter().chain(right.iter()).cloned().collect())
+}
+
+pub fn intersection(left: &[Interval], right: &[Interval]) -> Vec<Interval> {
+    complement(&union(&complement(left), &complement(right)))
+}
+
+pub fn difference(left: &[Interval], right: &[Interval]) -> Vec<Interval> {
+    intersection(left, &complement(right))
+}
+
+pub fn symmetric_difference(left: &[Interval], right: &[Interval]) -> Vec<Interval> {
+    union(&difference(left, right), &difference(right, left))
+}
+
+// the total length, which is undefined when it is unbounded
+pub fn length(intervals: &[Interval]) -> Result<Object, RuntimeError> {
+    let mut total = Object::Integer(0);
+    for interval in intervals {
+        if interval.start.as_real().unwrap().is_infinite() || interval.end.as_real().unwrap().is_infinite() {
+            return Ok(Object::Undefined)
+        };
+        total = (total + (interval.end.clone() - interval.start.clone())?)?;
+    }
+    Ok(total)
+}
+
+// a union of nothing but single points is just a finite set
+pub fn to_object(intervals: Vec<Interval>) -> Object {
+    if intervals.iter().all(Interval::is_point) {
+        let mut items = BTreeSet::new();
+        items.extend(intervals.into_iter().map(|interval| interval.start));
+        Object::set(items)
+    } else {
+        Object::Intervals(intervals)
+    }
+}
@@ -0,0 +1,93 @@
+use super::errors::RuntimeError;
+use super::object::Object;
+
+// matrices are stored as a list of rows; every row has the same length
+
+pub fn dot(left: &[Object], right: &[Object]) -> Result<Object, RuntimeError> {
+    if left.len() != right.len() {
+        return Err(RuntimeError::OperatorError(format!("Cannot take the dot product of vectors of length {} and {}.", left.len(), right.len())))
+    };
+    left.iter().zip(right.iter()).try_fold(Object::Integer(0), |acc, (a, b)| acc + (a.clone() * b.clone())?)
+}
+
+pub fn transpose(rows: &[Vec<Object>]) -> Vec<Vec<Object>> {
+    match rows.first() {
+        Some(first) => (0..first.len()).map(|column| rows.iter().map(|row| row[column].clone()).collect()).collect(),
+        None => vec![],
+    }
+}
+
+pub fn product(left: &[Vec<Object>], right: &[Vec<Object>]) -> Result<Vec<Vec<Object>>, RuntimeError> {
+    let columns = transpose(right);
+    left.iter().map(|row| columns.iter().map(|column| dot(row, column)).collect()).collect()
+}
+
+fn is_zero(obj: &Object) -> bool {
+    obj == &Object::Integer(0)
+}
+
+fn check_square(rows: &[Vec<Object>]) -> Result<(), RuntimeError> {
+    if rows.iter().any(|row| row.len() != rows.len()) {
+        Err(RuntimeError::TypeError(format!("Expected a square matrix got {}.", Object::Matrix { rows: rows.to_vec() })))
+    } else if rows.iter().flatten().any(|obj| !obj.is_number()) {
+        Err(RuntimeError::TypeError(format!("Expected a matrix of numbers got {}.", Object::Matrix { rows: rows.to_vec() })))
+    } else {
+        Ok(())
+    }
+}
+
+// gaussian elimination; exact entries give an exact determinant
+pub fn determinant(rows: &[Vec<Object>]) -> Result<Object, RuntimeError> {
+    check_square(rows)?;
+    let mut rows = rows.to_vec();
+    let size = rows.len();
+    let mut det = Object::Integer(1);
+    for column in 0..size {
+        match (column..size).find(|&row| !is_zero(&rows[row][column])) {
+            Some(pivot) if pivot != column => {
+                rows.swap(pivot, column);
+                det = (-det)?;
+            },
+            Some(_) => (),
+            None => return Ok(Object::Integer(0)),
+        };
+        det = (det * rows[column][column].clone())?;
+        let pivot_row = rows[column].clone();
+        for row in rows.iter_mut().skip(column + 1) {
+            let factor = (row[column].clone() / pivot_row[column].clone())?;
+            for (entry, pivot_entry) in row.iter_mut().zip(pivot_row.iter()).skip(column) {
+                *entry = (entry.clone() - (factor.clone() * pivot_entry.clone())?)?;
+            }
+        }
+    }
+    Ok(det)
+}
+
+// gauss-jordan elimination; singular matrices have no inverse so the result is undefined
+pub fn inverse(rows: &[Vec<Object>]) -> Result<Object, RuntimeError> {
+    check_square(rows)?;
+    let size = rows.len();
+    let mut rows = rows.iter().enumerate().map(|(i, row)| {
+        let mut row = row.clone();
+        row.extend((0..size).map(|j| Object::Integer(if i == j { 1 } else { 0 })));
+        row
+    }).collect::<Vec<_>>();
+    for column in 0..size {
+        match (column..size).find(|&row| !is_zero(&rows[row][column])) {
+            Some(pivot) => rows.swap(pivot, column),
+            None => return Ok(Object::Undefined),
+        };
+        let pivot = rows[column][column].clone();
+        rows[column] = rows[column].iter().map(|entry| entry.clone() / pivot.clone()).collect::<Result<_, _>>()?;
+        let pivot_row = rows[column].clone();
+        for (_, row) in rows.iter_mut().enumerate().filter(|(index, _)| *index != column) {
+            let factor = row[column].clone();
+            for (entry, pivot_entry) in row.iter_mut().zip(pivot_row.iter()) {
+                *entry = (entry.clone() - (factor.clone() * pivot_entry.clone())?)?;
+            }
+        }
+    }
+    Ok(Object::Matrix {
+        rows: rows.into_iter().map(|row| row[size..].to_vec()).collect()
+    })
+}
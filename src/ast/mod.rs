@@ -1,6 +1,7 @@
 pub mod environment;
 pub mod errors;
 pub mod parameter;
+pub mod generator;
 pub mod statement;
 pub mod parser;
 pub mod expression;
@@ -9,6 +10,15 @@ pub mod location;
 pub mod precedence;
 pub mod object;
 pub mod modules;
+pub mod linear_algebra;
+pub mod graph;
+pub mod interval;
+pub mod types;
+pub mod polynomial;
+pub mod units;
+pub mod enclosure;
+pub mod residue;
+pub mod composite;
 
 #[cfg(test)]
 pub mod tests;
\ No newline at end of file
@@ -1,21 +1,43 @@
-use super::environment::Environment;
-use super::object::Object;
+use super::environment::{Environment, NumericMode};
+use super::object::{Object, NumberSet};
 use super::errors::RuntimeError;
 use super::parameter::Parameter;
+use super::linear_algebra;
+use super::graph;
+use super::polynomial::Polynomial;
+use super::units;
+use super::enclosure::Enclosure;
+use super::residue;
 use std::f64::consts::{E, PI};
+use num_bigint::BigInt;
+use std::collections::BTreeMap;
 
 // a module is just a rust function that edits the current environment in some way
 pub type Module = fn(&mut Environment);
 
 // define some built-in "modules"
 pub fn math_constants_builtins(env: &mut Environment) {
-    env.set(&"PI".to_string(), &Object::Real(PI)).unwrap();
-    env.set(&"E".to_string(), &Object::Real(E)).unwrap();
+    let constant = match env.mode() {
+        NumericMode::Float => Object::Real,
+        NumericMode::Decimal => Object::decimal,
+    };
+    env.set(&"PI".to_string(), &constant(PI)).unwrap();
+    env.set(&"E".to_string(), &constant(E)).unwrap();
+    // decimals have no infinity, so this is always a float
+    env.set(&"inf".to_string(), &Object::Real(f64::INFINITY)).unwrap();
+}
+
+pub fn number_set_builtins(env: &mut Environment) {
+    env.set(&"N".to_string(), &Object::NumberSet(NumberSet::Naturals)).unwrap();
+    env.set(&"Z".to_string(), &Object::NumberSet(NumberSet::Integers)).unwrap();
+    env.set(&"Q".to_string(), &Object::NumberSet(NumberSet::Rationals)).unwrap();
+    env.set(&"R".to_string(), &Object::NumberSet(NumberSet::Reals)).unwrap();
+    env.set(&"C".to_string(), &Object::NumberSet(NumberSet::Complexes)).unwrap();
 }
 
 pub fn set_builtins(env: &mut Environment) {
     env.set(&"max".to_string(), &Object::BuiltinFunction {
-        parameters: vec![Parameter { name: "s".to_string() }],
+        parameters: vec![Parameter { name: "s".to_string(), domain: None }],
         body: |args| {
             match args.len() {
                 1 => match args.first().unwrap() {
@@ -25,6 +47,8 @@ pub fn set_builtins(env: &mut Environment) {
                             None => Ok(Object::Undefined)
                         }
                     },
+                    Object::Multiset { items, .. } => Ok(items.keys().next_back().cloned().unwrap_or(Object::Undefined)),
+                    Object::Range { end, .. } => Ok(Object::Integer(*end)),
                     obj => Err(RuntimeError::TypeError(format!("Expected a set got {}.", obj)))
                 },
                 num => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `max` got {}.", num)))
@@ -33,7 +57,7 @@ pub fn set_builtins(env: &mut Environment) {
     }).unwrap();
 
     env.set(&"min".to_string(), &Object::BuiltinFunction {
-        parameters: vec![Parameter { name: "s".to_string() }],
+        parameters: vec![Parameter { name: "s".to_string(), domain: None }],
         body: |args| {
             match args.len() {
                 1 => match args.first().unwrap() {
@@ -43,6 +67,8 @@ pub fn set_builtins(env: &mut Environment) {
                             None => Ok(Object::Undefined)
                         }
                     },
+                    Object::Multiset { items, .. } => Ok(items.keys().next().cloned().unwrap_or(Object::Undefined)),
+                    Object::Range { start, .. } => Ok(Object::Integer(*start)),
                     obj => Err(RuntimeError::TypeError(format!("Expected a set got {}.", obj)))
                 },
                 num => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `min` got {}.", num)))
@@ -51,7 +77,7 @@ pub fn set_builtins(env: &mut Environment) {
     }).unwrap();
 
     env.set(&"sum".to_string(), &Object::BuiltinFunction {
-        parameters: vec![Parameter { name: "s".to_string() }],
+        parameters: vec![Parameter { name: "s".to_string(), domain: None }],
         body: |args| {
             match args.len() {
                 1 => match args.first().unwrap() {
@@ -74,6 +100,16 @@ pub fn set_builtins(env: &mut Environment) {
                             Ok(Object::Undefined)
                         }
                     },
+                    // repeated members are added once per occurrence
+                    Object::Multiset { items, .. } if !items.is_empty() => {
+                        items.iter().try_fold(Object::Integer(0), |acc, (item, count)| acc + (item.clone() * Object::Integer(*count as i64))?)
+                    },
+                    Object::Multiset { .. } => Ok(Object::Undefined),
+                    // arithmetic series, so no need to visit every member
+                    Object::Range { start, end, step } => {
+                        let count = (BigInt::from(*end) - start) / step + 1;
+                        Ok(Object::integer((BigInt::from(*start) + end) * count / 2))
+                    },
                     obj => Err(RuntimeError::TypeError(format!("Expected a set got {}.", obj)))
                 },
                 num => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `sum` got {}.", num)))
@@ -82,7 +118,7 @@ pub fn set_builtins(env: &mut Environment) {
     }).unwrap();
 
     env.set(&"product".to_string(), &Object::BuiltinFunction {
-        parameters: vec![Parameter { name: "s".to_string() }],
+        parameters: vec![Parameter { name: "s".to_string(), domain: None }],
         body: |args| {
             match args.len() {
                 1 => match args.first().unwrap() {
@@ -105,6 +141,13 @@ pub fn set_builtins(env: &mut Environment) {
                             Ok(Object::Undefined)
                         }
                     },
+                    Object::Multiset { items, .. } if !items.is_empty() => {
+                        items.iter().try_fold(Object::Integer(1), |acc, (item, count)| acc * item.pow(&Object::Integer(*count as i64))?)
+                    },
+                    Object::Multiset { .. } => Ok(Object::Undefined),
+                    Object::Range { start, end, step } => {
+                        Object::range_members(*start, *end, *step).try_fold(Object::Integer(1), |acc, next| acc * next)
+                    },
                     obj => Err(RuntimeError::TypeError(format!("Expected a set got {}.", obj)))
                 },
                 num => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `product` got {}.", num)))
@@ -112,8 +155,23 @@ pub fn set_builtins(env: &mut Environment) {
         }
     }).unwrap();
 
+    env.set(&"count".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "s".to_string(), domain: None }, Parameter { name: "x".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                // sets hold each member once
+                [set, item] => match (set, item.in_(set)?) {
+                    (Object::Multiset { items, .. }, Object::Boolean(true)) => Ok(Object::Integer(items[item] as i64)),
+                    (_, Object::Boolean(true)) => Ok(Object::Integer(1)),
+                    _ => Ok(Object::Integer(0)),
+                },
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `count` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
     // env.set(&"power_set".to_string(), &Object::BuiltinFunction {
-    //     parameters: vec![Parameter { name: "s".to_string() }],
+    //     parameters: vec![Parameter { name: "s".to_string(), domain: None }],
     //     body: |args| {
     //         match args.len() {
     //             1 => match args.first().unwrap() {
@@ -126,4 +184,367 @@ pub fn set_builtins(env: &mut Environment) {
     //         }
     //     }
     // })?;
-}
\ No newline at end of file
+}
+pub fn linear_algebra_builtins(env: &mut Environment) {
+    env.set(&"transpose".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "m".to_string(), domain: None }],
+        body: |args| {
+            match args.len() {
+                1 => match args.first().unwrap() {
+                    Object::Matrix { rows } => Ok(Object::Matrix { rows: linear_algebra::transpose(rows) }),
+                    // a vector is a column, so its transpose is a single row
+                    Object::Vector { items } => Ok(Object::Matrix { rows: vec![items.clone()] }),
+                    obj => Err(RuntimeError::TypeError(format!("Expected a matrix got {}.", obj)))
+                },
+                num => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `transpose` got {}.", num)))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"det".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "m".to_string(), domain: None }],
+        body: |args| {
+            match args.len() {
+                1 => match args.first().unwrap() {
+                    Object::Matrix { rows } => linear_algebra::determinant(rows),
+                    obj => Err(RuntimeError::TypeError(format!("Expected a matrix got {}.", obj)))
+                },
+                num => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `det` got {}.", num)))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"inverse".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "m".to_string(), domain: None }],
+        body: |args| {
+            match args.len() {
+                1 => match args.first().unwrap() {
+                    Object::Matrix { rows } => linear_algebra::inverse(rows),
+                    Object::Residue { value, modulus } => Ok(residue::div(1, *value, *modulus)),
+                    obj => Err(RuntimeError::TypeError(format!("Expected a matrix or a residue got {}.", obj)))
+                },
+                num => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `inverse` got {}.", num)))
+            }
+        }
+    }).unwrap();
+}
+
+pub fn graph_builtins(env: &mut Environment) {
+    env.set(&"graph".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "nodes".to_string(), domain: None }, Parameter { name: "edges".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [nodes, edges] => graph::build(nodes, edges, false),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `graph` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"digraph".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "nodes".to_string(), domain: None }, Parameter { name: "edges".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [nodes, edges] => graph::build(nodes, edges, true),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `digraph` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"neighbours".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }, Parameter { name: "v".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, .. }, node] => graph::neighbours(adjacency, node),
+                [obj, _] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `neighbours` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"bfs".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }, Parameter { name: "v".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, .. }, node] => Ok(Object::Tuple { items: graph::traverse(adjacency, node, true)? }),
+                [obj, _] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `bfs` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"dfs".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }, Parameter { name: "v".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, .. }, node] => Ok(Object::Tuple { items: graph::traverse(adjacency, node, false)? }),
+                [obj, _] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `dfs` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"reachable".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }, Parameter { name: "u".to_string(), domain: None }, Parameter { name: "v".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, .. }, from, to] => {
+                    let order = graph::traverse(adjacency, from, true)?;
+                    Ok(Object::Boolean(order.contains(to)))
+                },
+                [obj, _, _] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 3 arguments to `reachable` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"shortest_path".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }, Parameter { name: "u".to_string(), domain: None }, Parameter { name: "v".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, .. }, from, to] => graph::shortest_path(adjacency, from, to),
+                [obj, _, _] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 3 arguments to `shortest_path` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"components".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, .. }] => Ok(graph::components(adjacency)),
+                [obj] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `components` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    // the number of edges at a vertex; polynomial_builtins overloads this with the degree of a polynomial
+    env.set(&"degree".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }, Parameter { name: "v".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, directed }, node] => graph::degree(adjacency, *directed, node),
+                [obj, _] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `degree` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"has_cycle".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Graph { adjacency, directed }] => Ok(Object::Boolean(graph::has_cycle(adjacency, *directed))),
+                [obj] => Err(RuntimeError::TypeError(format!("Expected a graph got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `has_cycle` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+}
+
+pub fn polynomial_builtins(env: &mut Environment) {
+    // from a function of one variable, or from coefficients listed from the highest power down
+    env.set(&"poly".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "f".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Function { parameters, expression, scope, .. }] if parameters.len() == 1 => {
+                    // the body is evaluated with the parameter standing for the polynomial `x` itself
+                    let variable = &parameters[0].name;
+                    let mut env = scope.new_child();
+                    env.set(variable, &Object::Polynomial(Polynomial::identity(variable)))?;
+                    let value = env.eval(expression)?;
+                    match Polynomial::from_object(&value, variable) {
+                        Some(polynomial) => Ok(Object::Polynomial(polynomial)),
+                        None => Err(RuntimeError::TypeError(format!("Expected a polynomial function got {}.", value))),
+                    }
+                },
+                [Object::Vector { items }] | [Object::Tuple { items }] => match items.iter().find(|item| !item.is_number()) {
+                    Some(item) => Err(RuntimeError::TypeError(format!("Expected numeric coefficients got {}.", item))),
+                    None => Ok(Object::Polynomial(Polynomial::new(items.iter().rev().cloned().collect(), "x"))),
+                },
+                [polynomial @ Object::Polynomial(_)] => Ok(polynomial.clone()),
+                [obj] => Err(RuntimeError::TypeError(format!("Expected a function of one variable or a list of coefficients got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `poly` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    // the highest power of a polynomial, an overload of the degree of a vertex `degree(g, v)`
+    env.overload(&"degree".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "p".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Polynomial(polynomial)] => Ok(polynomial.degree()),
+                [obj] => Err(RuntimeError::TypeError(format!("Expected a polynomial got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `degree` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    // long division, giving the quotient and the remainder
+    env.set(&"divide".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "p".to_string(), domain: None }, Parameter { name: "q".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [left, right] => match Polynomial::operands(left, right) {
+                    Some((dividend, divisor)) => {
+                        let (quotient, remainder) = dividend.divide(&divisor)?;
+                        Ok(Object::Tuple { items: vec![Object::Polynomial(quotient), Object::Polynomial(remainder)] })
+                    },
+                    None => Err(RuntimeError::TypeError(format!("Expected polynomials got {} and {}.", left, right))),
+                },
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `divide` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"derivative".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "p".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Polynomial(polynomial)] => Ok(Object::Polynomial(polynomial.derivative()?)),
+                [obj] => Err(RuntimeError::TypeError(format!("Expected a polynomial got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `derivative` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"gcd".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "p".to_string(), domain: None }, Parameter { name: "q".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [left, right] => match Polynomial::operands(left, right) {
+                    Some((left, right)) => Ok(Object::Polynomial(left.gcd(&right)?)),
+                    None => Err(RuntimeError::TypeError(format!("Expected polynomials got {} and {}.", left, right))),
+                },
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `gcd` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"roots".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "p".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Polynomial(polynomial)] => polynomial.roots(),
+                [obj] => Err(RuntimeError::TypeError(format!("Expected a polynomial got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `roots` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+}
+
+// the units and constants `import units` binds
+pub fn unit_definitions() -> BTreeMap<String, Object> {
+    let mut definitions = units::standard_units().unwrap().into_iter().collect::<BTreeMap<_, _>>();
+    definitions.extend(units::constants(&definitions).unwrap());
+    // `N` and `C` stay the number sets, so newtons and coulombs are also spelled out
+    definitions.insert("newton".to_string(), definitions["N"].clone());
+    definitions.insert("coulomb".to_string(), definitions["C"].clone());
+    definitions
+}
+
+// the name of a unit the program has already bound to something else
+pub fn units_clash(env: &Environment) -> Option<String> {
+    let builtins = Environment::with_mode(env.mode());
+    unit_definitions().into_iter()
+        .find(|(name, value)| builtins.get(name).is_none() && env.get(name).is_some_and(|bound| bound != *value))
+        .map(|(name, _)| name)
+}
+
+pub fn units_builtins(env: &mut Environment) {
+    for (name, value) in unit_definitions() {
+        // builtins like `N` and `C` keep their meaning
+        if env.get(&name).is_none() {
+            env.set(&name, &value).unwrap();
+        };
+    }
+}
+
+pub fn enclosure_builtins(env: &mut Environment) {
+    // a measurement `x` that may be off by up to `tolerance` either way
+    env.set(&"within".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "x".to_string(), domain: None }, Parameter { name: "tolerance".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [x, tolerance] if x.is_number() && tolerance.is_number() => {
+                    match (Enclosure::of(x), Enclosure::of(tolerance)) {
+                        (Some(x), Some(tolerance)) if tolerance.lo >= 0.0 => {
+                            Ok(Object::Enclosure(x.add(&Enclosure { lo: -tolerance.hi, hi: tolerance.hi })))
+                        },
+                        _ => Err(RuntimeError::TypeError(format!("Expected a real number and a tolerance of at least 0 got {} and {}.", x, tolerance))),
+                    }
+                },
+                [x, tolerance] => Err(RuntimeError::TypeError(format!("Expected a real number and a tolerance got {} and {}.", x, tolerance))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `within` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    env.set(&"enclosure".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "lo".to_string(), domain: None }, Parameter { name: "hi".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [lo, hi] => match (Enclosure::of(lo), Enclosure::of(hi)) {
+                    (Some(lo), Some(hi)) if !matches!(args[0], Object::Enclosure(_)) && !matches!(args[1], Object::Enclosure(_)) && lo.lo <= hi.hi => {
+                        Ok(Object::Enclosure(Enclosure { lo: lo.lo, hi: hi.hi }))
+                    },
+                    _ => Err(RuntimeError::TypeError(format!("Expected a lower bound no greater than the upper bound got {} and {}.", lo, hi))),
+                },
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `enclosure` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+
+    // the lower and upper bounds as reals
+    env.set(&"bounds".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "x".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [Object::Enclosure(enclosure)] => Ok(Object::Tuple { items: vec![Object::Real(enclosure.lo), Object::Real(enclosure.hi)] }),
+                [obj] => Err(RuntimeError::TypeError(format!("Expected an enclosure got {}.", obj))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 1 argument to `bounds` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+}
+
+pub fn residue_builtins(env: &mut Environment) {
+    // the one residue satisfying all of the given congruences, or undefined when they conflict
+    env.set(&"crt".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "residues".to_string(), domain: None }],
+        body: |args| {
+            let congruences = args.iter().map(|arg| match arg {
+                Object::Residue { value, modulus } => Ok((*value, *modulus)),
+                obj => Err(RuntimeError::TypeError(format!("Expected residues got {}.", obj))),
+            }).collect::<Result<Vec<_>, _>>()?;
+            if congruences.is_empty() {
+                return Err(RuntimeError::OperatorError("Expected at least 1 argument to `crt` got 0.".to_string()))
+            };
+            Ok(match residue::crt(&congruences)? {
+                Some((value, modulus)) => Object::Residue { value, modulus },
+                None => Object::Undefined,
+            })
+        }
+    }).unwrap();
+
+    // the smallest k with g^k ≡ h, or undefined when there is none
+    env.set(&"dlog".to_string(), &Object::BuiltinFunction {
+        parameters: vec![Parameter { name: "g".to_string(), domain: None }, Parameter { name: "h".to_string(), domain: None }],
+        body: |args| {
+            match args.as_slice() {
+                [base @ Object::Residue { .. }, target] => match residue::operands(base, target) {
+                    Some(operands) => {
+                        let (base, target, modulus) = operands?;
+                        Ok(residue::discrete_log(base, target, modulus)?.map_or(Object::Undefined, Object::Integer))
+                    },
+                    None => Err(RuntimeError::TypeError(format!("Expected an integer or residue to take the logarithm of got {}.", target))),
+                },
+                [base, _] => Err(RuntimeError::TypeError(format!("Expected a residue as the base got {}.", base))),
+                _ => Err(RuntimeError::OperatorError(format!("Expected 2 arguments to `dlog` got {}.", args.len())))
+            }
+        }
+    }).unwrap();
+}
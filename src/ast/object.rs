@@ -2,51 +2,145 @@ use super::errors::RuntimeError;
 use std::fmt::{Display, Formatter, self};
 use super::parameter::Parameter;
 use super::expression::Expression;
+use super::linear_algebra;
+use super::graph::{self, Adjacency};
+use super::interval::{self, Interval};
+use super::polynomial;
+use super::units::{self, Unit};
+use super::enclosure::{self, Enclosure};
+use super::residue;
+use super::composite::{self, Composite};
+use crate::interpreter::token::Token;
+use super::types::Type;
+use super::environment::Environment;
 use std::ops::{Add, Sub, Mul, Div, Neg, Not};
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
-use std::mem::{Discriminant, self};
+use std::collections::{BTreeMap, BTreeSet};
+use std::mem;
 use std::hash::{Hash, Hasher};
 use rust_decimal::prelude::*;
-use std::convert::From;
+use std::convert::{From, TryFrom};
+use num_bigint::BigInt;
+use num_integer::Integer as _;
+use num_traits::{Float as _, One, Signed, ToPrimitive, Zero};
 
-#[derive(Debug, Clone)]
-struct Condition {
-    expression: Expression,
+// exact powers larger than this many bits fall back to floating point
+pub(crate) const MAX_EXACT_POWER_BITS: u64 = 1 << 20;
+
+// factorials above this are too large to work out (20000! already has over 77000 digits)
+const MAX_FACTORIAL: i64 = 20_000;
+
+// decimal division and non-integer powers are rounded (half to even) to this many places
+const DECIMAL_PLACES: u32 = 20;
+
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Condition {
+    pub expression: Expression,
+}
+
+// the standard infinite sets of numbers, ordered so that each is a subset of the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NumberSet {
+    Naturals,  // including zero
+    Integers,
+    Rationals,
+    Reals,
+    Complexes,
+}
+
+impl Display for NumberSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberSet::Naturals => write!(f, "N"),
+            NumberSet::Integers => write!(f, "Z"),
+            NumberSet::Rationals => write!(f, "Q"),
+            NumberSet::Reals => write!(f, "R"),
+            NumberSet::Complexes => write!(f, "C"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Object {
     Integer(i64),
+    BigInteger(BigInt),  // only for values that do not fit in an i64; see Object::integer
     // Natural(u64),
-    // Rational {
-    //     numerator: i64,
-    //     denominator: i64
-    // },
-    // Complex {
-    //     real: f64,
-    //     imag: f64,
-    // },
+    Rational {
+        numerator: i64,
+        denominator: i64  // always positive and coprime with the numerator
+    },
+    BigRational {
+        numerator: BigInt,
+        denominator: BigInt  // only for fractions whose parts do not fit in an i64; see Object::rational
+    },
+    Complex {
+        real: f64,
+        imag: f64,  // never zero; see Object::complex
+    },
     Real(f64),
+    Decimal(Decimal),  // reals in decimal mode; see NumericMode
     Boolean(bool),
+    Atom {
+        name: String,
+        index: usize,  // position in the enumeration that declared it, which orders its members
+    },
     Undefined,
     Function {
         parameters: Vec<Parameter>,
+        codomain: Option<Expression>,  // the set results must belong to, if declared
         expression: Expression,
+        scope: Box<Environment>,  // where the function was defined, for looking up its free names
     },
     Set {
         items: BTreeSet<Object>,
-        kind: Option<Discriminant<Object>>,  // sets should contain elements of the same "type" (e.g. integers only)
+        kind: Type,  // the type every member has, such as Real; see Type::join
+    },
+    Multiset {
+        items: BTreeMap<Object, usize>,  // each member with its multiplicity, which is never zero
+        kind: Type,
+    },
+    SetBuilder {
+        variable: String,
+        parent_set: Box<Object>,
+        conditions: Vec<Condition>,
+        scope: Box<Environment>,  // where the set was built, for evaluating conditions later
+    },
+    Range {
+        start: i64,
+        end: i64,  // always a member, so the range is never empty
+        step: i64,  // always positive
+    },
+    NumberSet(NumberSet),
+    Intervals(Vec<Interval>),  // disjoint and in increasing order; see interval::normalise
+    Complement {
+        universe: Box<Object>,
+        excluded: Box<Object>,
+    },
+    Tuple {
+        items: Vec<Object>
+    },
+    Vector {
+        items: Vec<Object>
     },
-    // SetBuilder {
-    //     expression: Expression,
-
-    //     parent_set: Box<Object>,
-    //     conditions: Vec<Condition>
-    // },
-    // Tuple {
-    //     items: Vec<Object>
-    // },
+    Matrix {
+        rows: Vec<Vec<Object>>  // rows all have the same length
+    },
+    Graph {
+        adjacency: Adjacency,
+        directed: bool,
+    },
+    Polynomial(polynomial::Polynomial),
+    Quantity {
+        magnitude: Box<Object>,  // a number, in terms of the unit
+        unit: Unit,
+    },
+    Enclosure(Enclosure),
+    Residue {
+        value: i64,  // always at least 0 and less than the modulus
+        modulus: i64,
+    },
+    Overloads(Vec<Object>),  // functions declared under one name with different domains or arities
+    Composite(Composite),
     BuiltinFunction {
         parameters: Vec<Parameter>,
         body: fn(Vec<Object>) -> Result<Object, RuntimeError>
@@ -75,18 +169,69 @@ impl Hash for Object {
     fn hash<H: Hasher>(&self, state: &mut H) {
         use Object::*;
         match self {
-            Integer(value) => value.hash(state),
-            Real(value) => Decimal::from_f64(*value).unwrap().hash(state),
+            // equal numbers of different types must hash the same, so every finite real hashes as a fraction in lowest terms
+            Integer(_) | BigInteger(_) | Rational { .. } | BigRational { .. } | Real(_) | Decimal(_) => match self.fraction() {
+                Some((numerator, denominator)) => {
+                    let divisor = numerator.gcd(&denominator);
+                    (numerator / &divisor).hash(state);
+                    (denominator / divisor).hash(state);
+                },
+                None => self.as_real().unwrap().to_bits().hash(state),
+            },
+            Complex { real, imag } => {
+                rust_decimal::Decimal::from_f64(*real).unwrap().hash(state);
+                rust_decimal::Decimal::from_f64(*imag).unwrap().hash(state);
+            },
             Boolean(value) => value.hash(state),
+            Atom { name, index } => {
+                name.hash(state);
+                index.hash(state);
+            },
             Undefined => Undefined.hash(state),
-            Function { parameters, expression } => {
+            Function { parameters, codomain, expression, scope } => {
                 parameters.hash(state);
+                codomain.hash(state);
                 expression.hash(state);
+                scope.id().hash(state);
             },
-            Set { items, kind } => {
-                items.hash(state);
-                kind.hash(state);
-            }
+            // a range hashes the same as the set listing its members
+            Set { items, .. } => {
+                items.len().hash(state);
+                items.iter().for_each(|item| item.hash(state));
+            },
+            Multiset { items, .. } => items.hash(state),
+            SetBuilder { variable, parent_set, conditions, .. } => {
+                variable.hash(state);
+                parent_set.hash(state);
+                conditions.hash(state);
+            },
+            Range { start, end, step } => {
+                (((i128::from(*end) - i128::from(*start)) / i128::from(*step) + 1) as usize).hash(state);
+                Object::range_members(*start, *end, *step).for_each(|item| item.hash(state));
+            },
+            NumberSet(set) => set.hash(state),
+            Intervals(intervals) => intervals.hash(state),
+            Complement { universe, excluded } => {
+                universe.hash(state);
+                excluded.hash(state);
+            },
+            Tuple { items } => items.hash(state),
+            Vector { items } => items.hash(state),
+            Matrix { rows } => rows.hash(state),
+            Graph { adjacency, directed } => {
+                adjacency.hash(state);
+                directed.hash(state);
+            },
+            Polynomial(polynomial) => polynomial.hash(state),
+            // equal quantities may be written in different units
+            Quantity { magnitude, unit } => {
+                unit.dimension.hash(state);
+                units::base_value(magnitude, unit).hash(state);
+            },
+            Enclosure(enclosure) => enclosure.hash(state),
+            Residue { value, modulus } => (value, modulus).hash(state),
+            Overloads(definitions) => definitions.hash(state),
+            Composite(composite) => composite.hash(state),
             BuiltinFunction { parameters, body } => {
                 parameters.hash(state);
                 body.hash(state);
@@ -96,50 +241,490 @@ impl Hash for Object {
 }
 
 impl Object {
+    // integers that overflow an i64 are promoted to big integers and demoted again once they fit
+    pub fn integer(value: BigInt) -> Self {
+        match value.to_i64() {
+            Some(value) => Object::Integer(value),
+            None => Object::BigInteger(value),
+        }
+    }
+
+    // reduce a fraction to lowest terms, collapsing to an integer when possible
+    // and keeping big parts when the reduced parts do not fit in an i64
+    pub fn rational(numerator: BigInt, denominator: BigInt) -> Self {
+        use Object::*;
+        if denominator.is_zero() {
+            return Undefined
+        };
+        let divisor = numerator.gcd(&denominator);
+        let (mut numerator, mut denominator) = (numerator / &divisor, denominator / divisor);
+        if denominator.is_negative() {
+            numerator = -numerator;
+            denominator = -denominator;
+        };
+        if denominator.is_one() {
+            return Object::integer(numerator)
+        };
+        match (numerator.to_i64(), denominator.to_i64()) {
+            (Some(numerator), Some(denominator)) => Rational { numerator, denominator },
+            _ => BigRational { numerator, denominator },
+        }
+    }
+
+    // integers, big integers and rationals are exact
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Object::Integer(_) | Object::BigInteger(_) | Object::Rational { .. } | Object::BigRational { .. })
+    }
+
+    // exact (numerator, denominator) form of integers, rationals, decimals and finite floats,
+    // so that mixed comparisons are exact and equality stays transitive
+    pub(crate) fn fraction(&self) -> Option<(BigInt, BigInt)> {
+        use Object::*;
+        match self {
+            Integer(value) => Some((BigInt::from(*value), BigInt::one())),
+            BigInteger(value) => Some((value.clone(), BigInt::one())),
+            Rational { numerator, denominator } => Some((BigInt::from(*numerator), BigInt::from(*denominator))),
+            BigRational { numerator, denominator } => Some((numerator.clone(), denominator.clone())),
+            Decimal(value) => {
+                let mut mantissa = *value;
+                mantissa.set_scale(0).unwrap();
+                Some((BigInt::from(mantissa.to_i128().unwrap()), BigInt::from(10).pow(value.scale())))
+            },
+            // every finite float is mantissa * 2^exponent
+            Real(value) if value.is_finite() => {
+                let (mantissa, exponent, sign) = value.integer_decode();
+                let numerator = BigInt::from(mantissa) * sign;
+                Some(if exponent >= 0 {
+                    (numerator << exponent as usize, BigInt::one())
+                } else {
+                    (numerator, BigInt::one() << exponent.unsigned_abs() as usize)
+                })
+            },
+            _ => None,
+        }
+    }
+
+    // a decimal from the shortest representation of a float, so that `0.1` stays `0.1`
+    pub fn decimal(value: f64) -> Self {
+        match value.to_string().parse() {
+            Ok(value) => Object::Decimal(value),
+            Err(_) => Object::Real(value),
+        }
+    }
+
+    // the literal's digits exactly, rounded past the 28th, unless it is too large for a decimal
+    pub fn decimal_literal(text: &str) -> Self {
+        let value: f64 = text.parse().unwrap();
+        match text.parse() {
+            Ok(decimal) if value.abs() < 1e28 => Object::Decimal(decimal),
+            _ => Object::Real(value),
+        }
+    }
+
+    fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Object::Integer(value) => Some((*value).into()),
+            Object::BigInteger(value) => value.to_string().parse().ok(),
+            Object::Rational { numerator, denominator } => {
+                rust_decimal::Decimal::from(*numerator).checked_div((*denominator).into())
+                    .map(|value| value.round_dp_with_strategy(DECIMAL_PLACES, RoundingStrategy::MidpointNearestEven))
+            },
+            Object::BigRational { numerator, denominator } => {
+                numerator.to_string().parse::<Decimal>().ok()?.checked_div(denominator.to_string().parse().ok()?)
+                    .map(|value| value.round_dp_with_strategy(DECIMAL_PLACES, RoundingStrategy::MidpointNearestEven))
+            },
+            Object::Decimal(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // combine two decimals, falling back to floating point when the result overflows
+    fn decimal_operation(&self, rhs: &Self, operation: fn(Decimal, Decimal) -> Option<Decimal>, fallback: fn(f64, f64) -> f64) -> Self {
+        match operation(self.as_decimal().unwrap(), rhs.as_decimal().unwrap()) {
+            Some(value) => Object::Decimal(value),
+            None => Object::Real(fallback(self.as_real().unwrap(), rhs.as_real().unwrap())),
+        }
+    }
+
+    pub fn as_real(&self) -> Option<f64> {
+        use Object::*;
+        match self {
+            Integer(value) => Some(*value as f64),
+            BigInteger(value) => value.to_f64(),
+            Rational { numerator, denominator } => Some(*numerator as f64 / *denominator as f64),
+            // drop the same low bits from both parts so that neither overflows a float
+            BigRational { numerator, denominator } => {
+                let shift = numerator.bits().max(denominator.bits()).saturating_sub(1000);
+                Some((numerator >> shift).to_f64()? / (denominator >> shift).to_f64()?)
+            },
+            Real(value) => Some(*value),
+            Decimal(value) => value.to_f64(),
+            _ => None,
+        }
+    }
+
+    // complex numbers with no imaginary part are just reals
+    pub fn complex(real: f64, imag: f64) -> Self {
+        use Object::*;
+        if real.is_nan() || real.is_infinite() || imag.is_nan() || imag.is_infinite() {
+            Undefined
+        } else if imag == 0.0 {
+            Real(real)
+        } else {
+            Complex { real, imag }
+        }
+    }
+
+    fn as_complex(&self) -> Option<(f64, f64)> {
+        match self {
+            Object::Complex { real, imag } => Some((*real, *imag)),
+            obj => obj.as_real().map(|real| (real, 0.0)),
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.as_complex().is_some()
+    }
+
+    pub fn is_function(&self) -> bool {
+        matches!(self, Object::Function { .. } | Object::Overloads(_) | Object::Composite(_) | Object::BuiltinFunction { .. })
+    }
+
+    // apply an operation to every entry of a vector or matrix
+    fn map_entries(&self, operation: &dyn Fn(Object) -> Result<Object, RuntimeError>) -> Result<Self, RuntimeError> {
+        match self {
+            Object::Vector { items } => Ok(Object::Vector {
+                items: items.iter().cloned().map(operation).collect::<Result<_, _>>()?
+            }),
+            Object::Matrix { rows } => Ok(Object::Matrix {
+                rows: rows.iter().map(|row| row.iter().cloned().map(operation).collect()).collect::<Result<_, _>>()?
+            }),
+            obj => operation(obj.clone()),
+        }
+    }
+
+    // combine two vectors or matrices of the same shape entry by entry
+    fn zip_entries(&self, rhs: &Self, operation: fn(Object, Object) -> Result<Object, RuntimeError>) -> Result<Self, RuntimeError> {
+        match (self, rhs) {
+            (Object::Vector { items }, Object::Vector { items: items2 }) if items.len() == items2.len() => Ok(Object::Vector {
+                items: items.iter().cloned().zip(items2.iter().cloned()).map(|(a, b)| operation(a, b)).collect::<Result<_, _>>()?
+            }),
+            (Object::Matrix { rows }, Object::Matrix { rows: rows2 }) if rows.len() == rows2.len() && rows.first().map(Vec::len) == rows2.first().map(Vec::len) => Ok(Object::Matrix {
+                rows: rows.iter().zip(rows2.iter()).map(|(row, row2)| {
+                    row.iter().cloned().zip(row2.iter().cloned()).map(|(a, b)| operation(a, b)).collect()
+                }).collect::<Result<_, _>>()?
+            }),
+            (left, right) => Err(RuntimeError::OperatorError(format!("Cannot combine {} and {} with different shapes.", left, right))),
+        }
+    }
+
+    // the integers first, first + step, ... up to last; fewer than three members make a plain set
+    pub fn range(first: i64, last: i64, step: i64) -> Self {
+        let count = num_integer::Integer::div_floor(&(i128::from(last) - i128::from(first)), &i128::from(step)) + 1;
+        if count <= 2 {
+            let items = (0..count.max(0)).map(|k| Object::Integer((i128::from(first) + k * i128::from(step)) as i64)).collect::<BTreeSet<_>>();
+            return Object::set(items)
+        };
+        let final_member = (i128::from(first) + (count - 1) * i128::from(step)) as i64;
+        Object::Range {
+            start: first.min(final_member),
+            end: first.max(final_member),
+            step: step.abs(),
+        }
+    }
+
+    // a finite set of members that are already known to be comparable
+    pub fn set(items: BTreeSet<Object>) -> Self {
+        let kind = Type::of_members(&items).unwrap_or(Type::Unknown);
+        Object::Set { items, kind }
+    }
+
+    pub fn range_members(start: i64, end: i64, step: i64) -> impl Iterator<Item=Object> {
+        (0..=(i128::from(end) - i128::from(start)) / i128::from(step)).map(move |k| Object::Integer((i128::from(start) + k * i128::from(step)) as i64))
+    }
+
+    pub fn is_set(&self) -> bool {
+        matches!(self, Object::Set { .. } | Object::SetBuilder { .. } | Object::Range { .. } | Object::NumberSet(_) | Object::Intervals(_) | Object::Complement { .. })
+    }
+
+    fn is_finite(&self) -> bool {
+        match self {
+            Object::Set { .. } | Object::Range { .. } => true,
+            Object::SetBuilder { parent_set, .. } => parent_set.is_finite(),
+            _ => false,
+        }
+    }
+
+    fn is_integral(&self) -> bool {
+        match self {
+            Object::Integer(_) | Object::BigInteger(_) => true,
+            Object::Real(value) => value.fract() == 0.0,
+            Object::Decimal(value) => value.fract().is_zero(),
+            _ => false,
+        }
+    }
+
+    // the smallest standard number set containing this value; floating point reals
+    // are approximations so they only count as rational when they are whole numbers
+    fn number_set(&self) -> Option<NumberSet> {
+        match self {
+            obj if obj.is_integral() => Some(if obj >= &Object::Integer(0) { NumberSet::Naturals } else { NumberSet::Integers }),
+            Object::Rational { .. } | Object::BigRational { .. } | Object::Decimal(_) => Some(NumberSet::Rationals),
+            Object::Real(_) => Some(NumberSet::Reals),
+            Object::Complex { .. } => Some(NumberSet::Complexes),
+            _ => None,
+        }
+    }
+
+    // the number set that `\ A` is taken within, based on the members of A
+    fn universe(&self) -> Result<NumberSet, RuntimeError> {
+        match self {
+            Object::Set { items, .. } => items.iter().try_fold(NumberSet::Integers, |universe, item| match item.number_set() {
+                Some(set) => Ok(universe.max(set)),
+                None => Err(RuntimeError::TypeError(format!("Cannot take the complement of {}.", self))),
+            }),
+            Object::SetBuilder { parent_set, .. } => parent_set.universe(),
+            Object::Range { .. } => Ok(NumberSet::Integers),
+            Object::Intervals(_) => Ok(NumberSet::Reals),
+            Object::NumberSet(_) | Object::Complement { .. } => Ok(NumberSet::Complexes),
+            obj => Err(RuntimeError::TypeError(format!("Cannot take the complement of {}.", obj))),
+        }
+    }
+
+    pub fn complement(&self) -> Result<Self, RuntimeError> {
+        if let Object::Intervals(intervals) = self {
+            return Ok(interval::to_object(interval::complement(intervals)))
+        };
+        Ok(Object::Complement {
+            universe: Box::new(Object::NumberSet(self.universe()?)),
+            excluded: Box::new(self.clone()),
+        })
+    }
+
+    // intervals, along with the finite sets of reals
+    fn as_intervals(&self) -> Option<Vec<Interval>> {
+        match self {
+            Object::Intervals(intervals) => Some(intervals.clone()),
+            set if set.is_finite() => match set.materialise().ok()? {
+                Object::Set { items, .. } => items.iter().map(|item| Interval::point(item).ok()).collect(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // set operations use interval algebra once either side is an interval
+    fn interval_operands(&self, rhs: &Self) -> Option<(Vec<Interval>, Vec<Interval>)> {
+        if matches!(self, Object::Intervals(_)) || matches!(rhs, Object::Intervals(_)) {
+            Some((self.as_intervals()?, rhs.as_intervals()?))
+        } else {
+            None
+        }
+    }
+
+    // values whose types could not share a set cannot be compared either, apart from
+    // numbers, which are all comparable for equality, and integers with residues
+    pub fn comparable(&self, rhs: &Self) -> Result<(), RuntimeError> {
+        match (self, rhs) {
+            (Object::Undefined, _) | (_, Object::Undefined) => Ok(()),
+            (left, right) if units::operands(left, right) => units::comparable(left, right),
+            (left, right) if left.is_number() && right.is_number() => Ok(()),
+            (left, right) if residue::operands(left, right).is_some() => Ok(()),
+            (left, right) if Type::of(left).join(&Type::of(right)).is_some() => Ok(()),
+            (left, right) => Err(RuntimeError::TypeError(format!("Cannot compare {} and {}.", left, right))),
+        }
+    }
+
+    // like comparable, for `<`, `>`, `<=` and `>=`, which also need the values to have an order
+    pub fn orderable(&self, rhs: &Self) -> Result<(), RuntimeError> {
+        self.comparable(rhs)?;
+        match (Type::of(self), Type::of(rhs)) {
+            (left, right) if left.ordered() && right.ordered() => Ok(()),
+            _ => Err(RuntimeError::TypeError(format!("Cannot order {} and {}.", self, rhs))),
+        }
+    }
+
+    // membership where a value of another type is simply not a member
+    fn contained_in(&self, set: &Self) -> Result<bool, RuntimeError> {
+        match self.in_(set) {
+            Ok(Object::Boolean(value)) => Ok(value),
+            Ok(obj) => Err(RuntimeError::TypeError(format!("Expected a boolean from containment got {}.", obj))),
+            Err(RuntimeError::TypeError(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    // the members of a finite set that are (keep = true) or are not (keep = false) in another set
+    fn filter_members(&self, rhs: &Self, keep: bool) -> Result<Self, RuntimeError> {
+        match self.materialise()? {
+            Object::Set { items, .. } => {
+                let mut members = BTreeSet::new();
+                for item in items {
+                    if item.contained_in(rhs)? == keep {
+                        members.insert(item);
+                    };
+                }
+                Ok(Object::set(members))
+            },
+            obj => Err(RuntimeError::TypeError(format!("Cannot list the members of {}.", obj)))
+        }
+    }
+
+    // list the members of a set builder whose parent set is finite; other objects are returned as is
+    pub fn materialise(&self) -> Result<Self, RuntimeError> {
+        match self {
+            Object::SetBuilder { variable, parent_set, conditions, scope } => match parent_set.materialise()? {
+                Object::Set { items, .. } => {
+                    let mut members = BTreeSet::new();
+                    for item in items {
+                        if scope.satisfies(variable, &item, conditions)? {
+                            members.insert(item);
+                        };
+                    }
+                    Ok(Object::set(members))
+                },
+                obj => Err(RuntimeError::TypeError(format!("Cannot list the members of {}.", obj)))
+            },
+            Object::Range { start, end, step } => Ok(Object::Set {
+                items: Object::range_members(*start, *end, *step).collect(),
+                kind: Type::Integer,
+            }),
+            obj @ Object::NumberSet(_) | obj @ Object::Intervals(_) | obj @ Object::Complement { .. } => {
+                Err(RuntimeError::TypeError(format!("Cannot list the members of infinite set {}.", obj)))
+            },
+            obj => Ok(obj.clone()),
+        }
+    }
+
     pub fn is_undefined(&self) -> bool {
         mem::discriminant(self) == mem::discriminant(&Object::Undefined)
     }
 
     pub fn factorial(&self) -> Result<Self, RuntimeError> {
-        fn fact(num: &i64) -> i64 {
-            if num < &0 {
-                panic!("Expected positive integer.")
-            } else if num == &0 {
-                1
-            } else {
-                num * fact(&(num - 1))
-            }
-        }
         use Object::*;
         match self {
             Integer(value) => {
                 if value < &0 {
                     Err(RuntimeError::OperatorError(format!("Cannot use factorial on a negative integer {}.", value)))
+                } else if value > &MAX_FACTORIAL {
+                    Err(RuntimeError::OperatorError(format!("Cannot use factorial on {} (too large).", value)))
                 } else {
-                    Ok(Integer(fact(value)))
+                    Ok(Object::integer((1..=*value).fold(BigInt::one(), |acc, num| acc * num)))
                 }
             },
+            BigInteger(value) if value.is_negative() => Err(RuntimeError::OperatorError(format!("Cannot use factorial on a negative integer {}.", value))),
+            BigInteger(value) => Err(RuntimeError::OperatorError(format!("Cannot use factorial on {} (too large).", value))),
             obj => Err(RuntimeError::TypeError(format!("Cannot use factorial on {} (expected a positive integer or zero).", obj)))
         }
     }
 
     pub fn pow(&self, rhs: &Self) -> Result<Self, RuntimeError> {
         use Object::*;
-        if let Real(num) = match (self, rhs) {
-            // All the conversion here is pretty dodgy
-            (Integer(left), Integer(right)) => Real((*left as f64).powi(*right as i32)),
-            (Integer(left), Real(right)) => Real((*left as f64).powf(*right)),
-            (Real(left), Integer(right)) => Real(left.powi(*right as i32)),
-            (Real(left), Real(right)) => Real(left.powf(*right)),
-            _ => Undefined,
-        } {
-            if num.is_nan() || num.is_infinite() {
-                Ok(Undefined)
-            } else {
-                Ok(Real(num))
+        if self.is_function() || rhs.is_function() {
+            return composite::pow(self, rhs)
+        };
+        if let Polynomial(polynomial) = self {
+            return match rhs {
+                Integer(exponent) if *exponent >= 0 => Ok(Polynomial(polynomial.pow(*exponent as u64)?)),
+                _ => Err(RuntimeError::OperatorError(format!("Cannot raise polynomial {} to {} (expected a natural number).", self, rhs))),
             }
-        } else {
-            Err(RuntimeError::OperatorError(format!("Cannot exponentiate {} and {}.", self, rhs)))
+        };
+        if let Quantity { .. } = self {
+            return units::pow(self, rhs)
+        };
+        if let Residue { value, modulus } = self {
+            return match rhs {
+                Integer(exponent) => Ok(residue::pow(*value, &BigInt::from(*exponent), *modulus)),
+                BigInteger(exponent) => Ok(residue::pow(*value, exponent, *modulus)),
+                _ => Err(RuntimeError::OperatorError(format!("Cannot raise {} to {} (expected an integer power).", self, rhs))),
+            }
+        };
+        if let Enclosure(enclosure) = self {
+            return match rhs {
+                Integer(exponent) => Ok(enclosure.pow(*exponent).map_or(Undefined, Enclosure)),
+                _ => Err(RuntimeError::OperatorError(format!("Cannot raise {} to {} (expected an integer power).", self, rhs))),
+            }
+        };
+        if let (true, Integer(exponent)) = (self.is_exact(), rhs) {
+            // integer powers of exact numbers stay exact unless they would be enormous
+            let (numerator, denominator) = self.fraction().unwrap();
+            if let Ok(power) = u32::try_from(exponent.unsigned_abs()) {
+                if (numerator.bits() + denominator.bits()) * power as u64 <= MAX_EXACT_POWER_BITS {
+                    let (numerator, denominator) = (numerator.pow(power), denominator.pow(power));
+                    return Ok(if *exponent < 0 {
+                        Object::rational(denominator, numerator)
+                    } else {
+                        Object::rational(numerator, denominator)
+                    })
+                }
+            }
+        };
+        if let (Decimal(base), Integer(exponent)) = (self, rhs) {
+            // integer powers of decimals by repeated squaring
+            let (mut base, mut power, mut result) = (*base, exponent.unsigned_abs(), Some(rust_decimal::Decimal::one()));
+            while power > 0 && result.is_some() {
+                if power % 2 == 1 {
+                    result = result.and_then(|result| result.checked_mul(base));
+                };
+                power /= 2;
+                if power > 0 {
+                    match base.checked_mul(base) {
+                        Some(square) => base = square,
+                        None => result = None,
+                    }
+                };
+            }
+            match result {
+                Some(result) if *exponent >= 0 => return Ok(Decimal(result)),
+                Some(result) if !result.is_zero() => {
+                    let one = rust_decimal::Decimal::one();
+                    if let Some(value) = one.checked_div(result) {
+                        return Ok(Decimal(value.round_dp_with_strategy(DECIMAL_PLACES, RoundingStrategy::MidpointNearestEven)))
+                    }
+                },
+                Some(_) => return Ok(Undefined),
+                None => (),
+            }
+        };
+        if let (Some(left), Some(right)) = (self.as_real(), rhs.as_real()) {
+            // negative bases with fractional exponents have complex results
+            if left >= 0.0 || right.fract() == 0.0 {
+                let num = left.powf(right);
+                return if num.is_nan() || num.is_infinite() {
+                    Ok(Undefined)
+                } else if let (Decimal(_), _) | (_, Decimal(_)) = (self, rhs) {
+                    match Object::decimal(num) {
+                        Decimal(value) => Ok(Decimal(value.round_dp_with_strategy(DECIMAL_PLACES, RoundingStrategy::MidpointNearestEven))),
+                        obj => Ok(obj),
+                    }
+                } else {
+                    Ok(Real(num))
+                }
+            }
+        };
+        match (self.as_complex(), rhs.as_complex()) {
+            (Some((0.0, 0.0)), Some((real, _))) => Ok(if real > 0.0 { Integer(0) } else { Undefined }),
+            (Some((a, b)), Some((c, d))) => {
+                // z ^ w = e ^ (w * ln(z))
+                let (log_modulus, arg) = (a.hypot(b).ln(), b.atan2(a));
+                let (x, y) = (c * log_modulus - d * arg, d * log_modulus + c * arg);
+                let modulus = x.exp();
+                let (real, imag) = (modulus * y.cos(), modulus * y.sin());
+                // drop the rounding noise left behind by the trigonometric functions
+                let noise = modulus * f64::EPSILON * 4.0;
+                Ok(Object::complex(
+                    if real.abs() < noise { 0.0 } else { real },
+                    if imag.abs() < noise { 0.0 } else { imag },
+                ))
+            },
+            _ => Err(RuntimeError::OperatorError(format!("Cannot exponentiate {} and {}.", self, rhs)))
+        }
+    }
+
+    pub fn imaginary(&self) -> Result<Self, RuntimeError> {
+        match self.as_complex() {
+            Some((real, imag)) => Ok(Object::complex(-imag, real)),
+            None => Err(RuntimeError::OperatorError(format!("Cannot make {} imaginary.", self)))
         }
     }
 
@@ -154,9 +739,24 @@ impl Object {
     pub fn abs(&self) -> Result<Self, RuntimeError> {
         use Object::*;
         match self {
-            Integer(value) => Ok(Integer(value.abs())),
+            Integer(value) => Ok(value.checked_abs().map_or_else(|| Object::integer(BigInt::from(*value).abs()), Integer)),
+            BigInteger(value) => Ok(Object::integer(value.abs())),
+            Rational { numerator, denominator } => Ok(Object::rational(BigInt::from(*numerator).abs(), BigInt::from(*denominator))),
+            BigRational { numerator, denominator } => Ok(Object::rational(numerator.abs(), denominator.clone())),
             Real(value) => Ok(Real(value.abs())),
+            Decimal(value) => Ok(Decimal(value.abs())),
+            Complex { real, imag } => Ok(Real(real.hypot(*imag))),
             Set { items, .. } => Ok(Integer(items.len() as i64)),
+            Multiset { items, .. } => Ok(Integer(items.values().sum::<usize>() as i64)),
+            SetBuilder { .. } => self.materialise()?.abs(),
+            Range { start, end, step } => Ok(Object::integer((BigInt::from(*end) - start) / step + 1)),
+            Intervals(intervals) => interval::length(intervals),
+            Tuple { items } => Ok(Integer(items.len() as i64)),
+            Quantity { magnitude, unit } => units::quantity(magnitude.abs()?, unit.clone()),
+            Enclosure(enclosure) => Ok(Enclosure(enclosure.abs())),
+            // the euclidean norm of a vector and the determinant of a matrix
+            Vector { items } => linear_algebra::dot(items, items)?.pow(&Object::Real(0.5)),
+            Matrix { rows } => linear_algebra::determinant(rows),
             obj => Err(RuntimeError::OperatorError(format!("Cannot take absolute value of {}.", obj)))
         }
     }
@@ -189,8 +789,28 @@ impl Object {
     pub fn modulo(&self, rhs: &Self) -> Result<Self, RuntimeError> {
         use Object::*;
         match (self, rhs) {
-            // use Rem trait instead
-            (Integer(left), Integer(right)) => Ok(Integer(left % right)),
+            (_, Integer(0)) => Ok(Undefined),
+            // i64::MIN % -1 is the only remainder that overflows
+            (Integer(left), Integer(right)) => Ok(Integer(left.checked_rem(*right).unwrap_or(0))),
+            // truncated remainder, matching the sign convention of integers
+            (left, right) if left.is_exact() && right.is_exact() => {
+                let ((n1, d1), (n2, d2)) = (left.fraction().unwrap(), right.fraction().unwrap());
+                if n2.is_zero() {
+                    Ok(Undefined)
+                } else {
+                    Ok(Object::rational((n1 * &d2) % (n2 * &d1), d1 * d2))
+                }
+            },
+            (left, right) if left.as_decimal().is_some() && right.as_decimal().is_some() => {
+                match left.as_decimal().unwrap().checked_rem(right.as_decimal().unwrap()) {
+                    Some(value) => Ok(Decimal(value)),
+                    None => Ok(Undefined),
+                }
+            },
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => {
+                let value = left.as_real().unwrap() % right.as_real().unwrap();
+                if value.is_nan() { Ok(Undefined) } else { Ok(Real(value)) }
+            },
             (left, right) => Err(RuntimeError::OperatorError(format!("Cannot take modulo of {} and {}.", left, right)))
         }
     }
@@ -198,43 +818,129 @@ impl Object {
     pub fn in_(&self, rhs: &Self) -> Result<Self, RuntimeError> {
         use Object::*;
         match (self, rhs) {
-            (any, Set { items, kind }) => {
-                match kind {
-                    None => Ok(Boolean(false)),
-                    Some(disc) => {
-                        if disc == &mem::discriminant(any) {
-                            Ok(Boolean(items.contains(any)))
-                        } else {
-                            Err(RuntimeError::TypeError("Cannot check for containment with differing types.".into()))
-                        }
-                    }
-                }
+            (any, Set { items, kind }) => match kind.join(&Type::of(any)) {
+                Some(_) => Ok(Boolean(items.contains(any))),
+                None => Err(RuntimeError::TypeError(format!("Cannot check whether a value of type {} is in {}.", Type::of(any), Type::of(rhs)))),
+            },
+            (any, Multiset { items, kind }) => match kind.join(&Type::of(any)) {
+                Some(_) => Ok(Boolean(items.contains_key(any))),
+                None => Err(RuntimeError::TypeError(format!("Cannot check whether a value of type {} is in {}.", Type::of(any), Type::of(rhs)))),
+            },
+            // membership is decided by the conditions without listing the members
+            (any, SetBuilder { variable, parent_set, conditions, scope }) => match any.in_(parent_set)? {
+                Boolean(true) => Ok(Boolean(scope.satisfies(variable, any, conditions)?)),
+                obj => Ok(obj),
+            },
+            (Integer(value), Range { start, end, step }) => {
+                Ok(Boolean(start <= value && value <= end && (i128::from(*value) - i128::from(*start)) % i128::from(*step) == 0))
+            },
+            (any, range @ Range { .. }) if any.is_number() => match any.as_real() {
+                Some(value) if any.is_integral() && value.abs() < 2f64.powi(63) => Integer(value as i64).in_(range),
+                _ => Ok(Boolean(false)),
+            },
+            (any, Intervals(intervals)) => Ok(Boolean(intervals.iter().any(|interval| interval.contains(any)))),
+            (any, NumberSet(set)) => Ok(Boolean(any.number_set().is_some_and(|smallest| smallest <= *set))),
+            (any, Complement { universe, excluded }) => match any.in_(universe)? {
+                Boolean(true) => Ok(Boolean(!any.contained_in(excluded)?)),
+                obj => Ok(obj),
             },
             (left, right) => Err(RuntimeError::OperatorError(format!("Cannot use containment operator for {} and {}.", left, right)))
         }
     }
 
+    // zero-based indexing; out of range indices are undefined
+    pub fn index(&self, index: &Self) -> Result<Self, RuntimeError> {
+        use Object::*;
+        match (self, index) {
+            (Tuple { items }, Integer(index)) => {
+                Ok(usize::try_from(*index).ok().and_then(|index| items.get(index)).cloned().unwrap_or(Undefined))
+            },
+            (Vector { items }, Integer(index)) => {
+                Ok(usize::try_from(*index).ok().and_then(|index| items.get(index)).cloned().unwrap_or(Undefined))
+            },
+            // a single index into a matrix gives a row
+            (Matrix { rows }, Integer(index)) => {
+                Ok(usize::try_from(*index).ok().and_then(|index| rows.get(index)).map_or(Undefined, |row| Vector { items: row.clone() }))
+            },
+            (Tuple { .. }, index) | (Vector { .. }, index) | (Matrix { .. }, index) => {
+                Err(RuntimeError::TypeError(format!("Indices must be integers, got {}.", index)))
+            },
+            (obj, _) => Err(RuntimeError::OperatorError(format!("Cannot index into {}.", obj)))
+        }
+    }
+
     pub fn pm(&self, rhs: &Self) -> Result<Self, RuntimeError> {
         let one = self.clone().add(rhs.clone())?;
         let two = self.clone().sub(rhs.clone())?;
         let mut items = BTreeSet::new();
-        let kind = Some(mem::discriminant(&one));
         items.insert(one);
         items.insert(two);
-        Ok(Object::Set {
-            items,
-            kind
-        })
+        Ok(Object::set(items))
+    }
+
+    // a finite set as a multiset where each member occurs once
+    pub fn as_multiset(&self) -> Result<Self, RuntimeError> {
+        match self {
+            Object::Multiset { .. } => Ok(self.clone()),
+            set if set.is_finite() => match set.materialise()? {
+                Object::Set { items, kind } => {
+                    let mut counts = BTreeMap::new();
+                    counts.extend(items.into_iter().map(|item| (item, 1)));
+                    Ok(Object::Multiset { items: counts, kind })
+                },
+                obj => Err(RuntimeError::TypeError(format!("Expected a multiset got {}.", obj))),
+            },
+            obj => Err(RuntimeError::TypeError(format!("Expected a multiset got {}.", obj))),
+        }
+    }
+
+    // combines the multiplicities of each member when either side is a multiset; sets count each member once
+    fn multiset_operation(&self, rhs: &Self, name: &str, combine: fn(usize, usize) -> usize) -> Option<Result<Self, RuntimeError>> {
+        if !matches!(self, Object::Multiset { .. }) && !matches!(rhs, Object::Multiset { .. }) {
+            return None
+        };
+        let combined = || {
+            let ((items1, kind1), (items2, kind2)) = match (self.as_multiset(), rhs.as_multiset()) {
+                (Ok(Object::Multiset { items, kind }), Ok(Object::Multiset { items: items2, kind: kind2 })) => ((items, kind), (items2, kind2)),
+                _ => return Err(RuntimeError::OperatorError(format!("Cannot use multiset-{} for {} and {}.", name, self, rhs))),
+            };
+            if kind1.join(&kind2).is_none() {
+                return Err(RuntimeError::OperatorError(format!("Cannot use multiset-{} for {} and {}.", name, self, rhs)))
+            };
+            let mut items = BTreeMap::new();
+            for item in items1.keys().chain(items2.keys()) {
+                let count = combine(items1.get(item).copied().unwrap_or(0), items2.get(item).copied().unwrap_or(0));
+                if count > 0 {
+                    items.insert(item.clone(), count);
+                };
+            }
+            let kind = Type::of_members(items.keys()).unwrap_or(Type::Unknown);
+            Ok(Object::Multiset { items, kind })
+        };
+        Some(combined())
     }
 
     pub fn set_difference(&self, rhs: &Self) -> Result<Self, RuntimeError> {
-        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (self, rhs) {
-            if kind1 == kind2 {
-                let items = items1.difference(&items2).cloned().collect::<BTreeSet<Object>>();
-                Ok(Object::Set {
-                    items: items,
-                    kind: *kind1
-                })
+        if let Some(result) = self.multiset_operation(rhs, "difference", usize::saturating_sub) {
+            return result
+        };
+        if let Some((left, right)) = self.interval_operands(rhs) {
+            return Ok(interval::to_object(interval::difference(&left, &right)))
+        };
+        if self.is_set() && rhs.is_set() && !self.is_finite() {
+            return Ok(Object::Complement {
+                universe: Box::new(self.clone()),
+                excluded: Box::new(rhs.clone()),
+            })
+        } else if self.is_finite() && rhs.is_set() && !rhs.is_finite() {
+            return self.filter_members(rhs, false)
+        };
+        let (left, right) = (self.materialise()?, rhs.materialise()?);
+        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (&left, &right) {
+            if kind1.join(kind2).is_some() {
+                let mut items = BTreeSet::new();
+                items.extend(items1.difference(items2).cloned());
+                Ok(Object::set(items))
             } else {
                 Err(RuntimeError::OperatorError(format!("Cannot use set-difference for {} and {}.", self, rhs)))
             }
@@ -244,13 +950,25 @@ impl Object {
     }
 
     pub fn set_intersection(&self, rhs: &Self) -> Result<Self, RuntimeError> {
-        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (self, rhs) {
-            if kind1 == kind2 {
-                let items = items1.intersection(&items2).cloned().collect::<BTreeSet<Object>>();
-                Ok(Object::Set {
-                    items: items,
-                    kind: *kind1
-                })
+        if let Some(result) = self.multiset_operation(rhs, "intersection", usize::min) {
+            return result
+        };
+        if let Some((left, right)) = self.interval_operands(rhs) {
+            return Ok(interval::to_object(interval::intersection(&left, &right)))
+        };
+        match (self, rhs) {
+            (Object::NumberSet(set), Object::NumberSet(set2)) => return Ok(Object::NumberSet(*set.min(set2))),
+            (finite, infinite) | (infinite, finite) if finite.is_finite() && infinite.is_set() && !infinite.is_finite() => {
+                return finite.filter_members(infinite, true)
+            },
+            _ => (),
+        };
+        let (left, right) = (self.materialise()?, rhs.materialise()?);
+        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (&left, &right) {
+            if kind1.join(kind2).is_some() {
+                let mut items = BTreeSet::new();
+                items.extend(items1.intersection(items2).cloned());
+                Ok(Object::set(items))
             } else {
                 Err(RuntimeError::OperatorError(format!("Cannot use set-intersection for {} and {}.", self, rhs)))
             }
@@ -260,13 +978,18 @@ impl Object {
     }
 
     pub fn set_symmetric_difference(&self, rhs: &Self) -> Result<Self, RuntimeError> {
-        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (self, rhs) {
-            if kind1 == kind2 {
-                let items = items1.symmetric_difference(&items2).cloned().collect::<BTreeSet<Object>>();
-                Ok(Object::Set {
-                    items: items,
-                    kind: *kind1
-                })
+        if let Some(result) = self.multiset_operation(rhs, "symmetric difference", |left, right| left.max(right) - left.min(right)) {
+            return result
+        };
+        if let Some((left, right)) = self.interval_operands(rhs) {
+            return Ok(interval::to_object(interval::symmetric_difference(&left, &right)))
+        };
+        let (left, right) = (self.materialise()?, rhs.materialise()?);
+        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (&left, &right) {
+            if kind1.join(kind2).is_some() {
+                let mut items = BTreeSet::new();
+                items.extend(items1.symmetric_difference(items2).cloned());
+                Ok(Object::set(items))
             } else {
                 Err(RuntimeError::OperatorError(format!("Cannot use set-symmetric difference for {} and {}.", self, rhs)))
             }
@@ -276,13 +999,29 @@ impl Object {
     }
 
     pub fn set_union(&self, rhs: &Self) -> Result<Self, RuntimeError> {
-        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (self, rhs) {
-            if kind1 == kind2 {
-                let items = items1.union(&items2).cloned().collect::<BTreeSet<Object>>();
-                Ok(Object::Set {
-                    items: items,
-                    kind: *kind1
-                })
+        if let Some(result) = self.multiset_operation(rhs, "union", usize::max) {
+            return result
+        };
+        if let Some((left, right)) = self.interval_operands(rhs) {
+            return Ok(interval::to_object(interval::union(&left, &right)))
+        };
+        match (self, rhs) {
+            (Object::NumberSet(set), Object::NumberSet(set2)) => return Ok(Object::NumberSet(*set.max(set2))),
+            (finite, infinite) | (infinite, finite) if finite.is_finite() && infinite.is_set() && !infinite.is_finite() => {
+                return if finite.is_subset(infinite)? == Object::Boolean(true) {
+                    Ok(infinite.clone())
+                } else {
+                    Err(RuntimeError::OperatorError(format!("Cannot use set-union for {} and {}.", self, rhs)))
+                }
+            },
+            _ => (),
+        };
+        let (left, right) = (self.materialise()?, rhs.materialise()?);
+        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (&left, &right) {
+            if kind1.join(kind2).is_some() {
+                let mut items = BTreeSet::new();
+                items.extend(items1.union(items2).cloned());
+                Ok(Object::set(items))
             } else {
                 Err(RuntimeError::OperatorError(format!("Cannot use set-union for {} and {}.", self, rhs)))
             }
@@ -292,8 +1031,34 @@ impl Object {
     }
 
     pub fn is_subset(&self, rhs: &Self) -> Result<Self, RuntimeError> {
-        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (self, rhs) {
-            if kind1 == kind2 {
+        if let Some((left, right)) = self.interval_operands(rhs) {
+            return Ok(Object::Boolean(interval::difference(&left, &right).is_empty()))
+        };
+        match (self, rhs) {
+            (Object::Intervals(_), Object::NumberSet(set)) => return Ok(Object::Boolean(*set >= NumberSet::Reals)),
+            (Object::NumberSet(set), Object::NumberSet(set2)) => return Ok(Object::Boolean(set <= set2)),
+            (Object::Range { start, .. }, Object::NumberSet(set)) => {
+                return Ok(Object::Boolean(*set >= NumberSet::Integers || *start >= 0))
+            },
+            (finite, infinite) if finite.is_finite() && infinite.is_set() && !infinite.is_finite() => {
+                return match finite.filter_members(infinite, false)? {
+                    Object::Set { items, .. } => Ok(Object::Boolean(items.is_empty())),
+                    _ => unreachable!(),
+                }
+            },
+            (infinite, finite) if infinite.is_set() && !infinite.is_finite() && finite.is_finite() => return Ok(Object::Boolean(false)),
+            // an infinite set builder or complement is a subset of anything containing its parent set
+            (Object::SetBuilder { parent_set: universe, .. }, set) | (Object::Complement { universe, .. }, set) if set.is_set() => {
+                return match universe.is_subset(set)? {
+                    Object::Boolean(true) => Ok(Object::Boolean(true)),
+                    _ => Err(RuntimeError::OperatorError(format!("Cannot decide whether {} is a subset of {}.", self, rhs)))
+                }
+            },
+            _ => (),
+        };
+        let (left, right) = (self.materialise()?, rhs.materialise()?);
+        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (&left, &right) {
+            if kind1.join(kind2).is_some() {
                 Ok(Object::Boolean(items1.is_subset(&items2)))
             } else {
                 Err(RuntimeError::OperatorError(format!("Cannot use set-subset for {} and {}.", self, rhs)))
@@ -304,8 +1069,19 @@ impl Object {
     }
 
     pub fn is_proper_subset(&self, rhs: &Self) -> Result<Self, RuntimeError> {
-        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (self, rhs) {
-            if kind1 == kind2 {
+        if let Some((left, right)) = self.interval_operands(rhs) {
+            return Ok(Object::Boolean(interval::difference(&left, &right).is_empty() && !interval::difference(&right, &left).is_empty()))
+        };
+        match (self, rhs) {
+            (Object::Intervals(_), Object::NumberSet(set)) => return Ok(Object::Boolean(*set >= NumberSet::Reals)),
+            (Object::NumberSet(set), Object::NumberSet(set2)) => return Ok(Object::Boolean(set < set2)),
+            (finite, infinite) if finite.is_finite() && infinite.is_set() && !infinite.is_finite() => return finite.is_subset(infinite),
+            (infinite, finite) if infinite.is_set() && !infinite.is_finite() && finite.is_finite() => return Ok(Object::Boolean(false)),
+            _ => (),
+        };
+        let (left, right) = (self.materialise()?, rhs.materialise()?);
+        if let (Object::Set { items: items1, kind: kind1 }, Object::Set { items: items2, kind: kind2 }) = (&left, &right) {
+            if kind1.join(kind2).is_some() {
                 Ok(Object::Boolean(items1.is_subset(&items2) && items1.len() != items2.len()))
             } else {
                 Err(RuntimeError::OperatorError(format!("Cannot use set-propert subset for {} and {}.", self, rhs)))
@@ -321,15 +1097,31 @@ impl Display for Object {
         use Object::*;
         match self {
             Integer(value) => write!(f, "{}", value),
+            BigInteger(value) => write!(f, "{}", value),
+            Rational { numerator, denominator } => write!(f, "{}/{}", numerator, denominator),
+            BigRational { numerator, denominator } => write!(f, "{}/{}", numerator, denominator),
             Real(value) => write!(f, "{}", value),
+            Decimal(value) => write!(f, "{}", value.normalize()),
+            Complex { real, imag } => {
+                if *real == 0.0 {
+                    write!(f, "{}i", imag)
+                } else if *imag < 0.0 {
+                    write!(f, "{} - {}i", real, -imag)
+                } else {
+                    write!(f, "{} + {}i", real, imag)
+                }
+            },
             Boolean(value) => write!(f, "{}", value),
+            Atom { name, .. } => write!(f, "{}", name),
             Undefined => write!(f, "undefined"),
             Function {
                 parameters,
+                codomain,
                 expression,
+                ..
             } => write!(
                 f,
-                "fn({}) = {};",
+                "fn({}){} = {}",
                 parameters.iter().fold(String::new(), |mut acc, param| {
                     if !acc.is_empty() {
                         acc.push_str(", ");
@@ -337,6 +1129,7 @@ impl Display for Object {
                     acc.push_str(&param.to_string());
                     acc
                 }),
+                codomain.as_ref().map_or(String::new(), |codomain| format!(" -> {}", codomain)),
                 expression
             ),
             Set { items, .. } => write!(f, "{{ {} }}", items.iter().fold(String::new(), |mut acc, member| {
@@ -346,9 +1139,87 @@ impl Display for Object {
                 acc.push_str(&member.to_string());
                 acc
             })),
+            Multiset { items, .. } => write!(f, "{{: {} :}}", items.iter().fold(String::new(), |mut acc, (member, count)| {
+                for _ in 0..*count {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&member.to_string());
+                }
+                acc
+            })),
+            SetBuilder { variable, parent_set, conditions, .. } => write!(f, "{{ {} in {}: {} }}", variable, parent_set, conditions.iter().fold(String::new(), |mut acc, condition| {
+                if !acc.is_empty() {
+                    acc.push_str(", ");
+                };
+                acc.push_str(&condition.expression.to_string());
+                acc
+            })),
+            Range { start, end, step: 1 } => write!(f, "{{ {}, ..., {} }}", start, end),
+            Range { start, end, step } => write!(f, "{{ {}, {}, ..., {} }}", start, start + step, end),
+            NumberSet(set) => write!(f, "{}", set),
+            Intervals(intervals) => write!(f, "{}", intervals.iter().fold(String::new(), |mut acc, interval| {
+                if !acc.is_empty() {
+                    acc.push_str(" \\/ ");
+                };
+                acc.push_str(&interval.to_string());
+                acc
+            })),
+            Complement { universe, excluded } => write!(f, "{} \\ {}", universe, excluded),
+            Tuple { items } if items.len() == 1 => write!(f, "({},)", items[0]),
+            Vector { items } => write!(f, "[{}]", items.iter().fold(String::new(), |mut acc, member| {
+                if !acc.is_empty() {
+                    acc.push_str(", ");
+                };
+                acc.push_str(&member.to_string());
+                acc
+            })),
+            Matrix { rows } => write!(f, "[{}]", rows.iter().fold(String::new(), |mut acc, row| {
+                if !acc.is_empty() {
+                    acc.push_str(", ");
+                };
+                acc.push_str(&Vector { items: row.clone() }.to_string());
+                acc
+            })),
+            Tuple { items } => write!(f, "({})", items.iter().fold(String::new(), |mut acc, member| {
+                if !acc.is_empty() {
+                    acc.push_str(", ");
+                };
+                acc.push_str(&member.to_string());
+                acc
+            })),
+            Graph { adjacency, directed } => write!(
+                f,
+                "{}({{ {} }}, {{ {} }})",
+                if *directed { "digraph" } else { "graph" },
+                adjacency.keys().fold(String::new(), |mut acc, node| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&node.to_string());
+                    acc
+                }),
+                graph::edges(adjacency, *directed).iter().fold(String::new(), |mut acc, edge| {
+                    if !acc.is_empty() {
+                        acc.push_str(", ");
+                    };
+                    acc.push_str(&edge.to_string());
+                    acc
+                })
+            ),
+            Polynomial(polynomial) => write!(f, "{}", polynomial),
+            // fractions are bracketed so that `(3/2) km` is not read as 3/(2 km)
+            Quantity { magnitude, unit } => match **magnitude {
+                Rational { .. } | BigRational { .. } | Complex { .. } => write!(f, "({}) {}", magnitude, unit),
+                _ => write!(f, "{} {}", magnitude, unit),
+            },
+            Enclosure(enclosure) => write!(f, "{}", enclosure),
+            Residue { value, modulus } => write!(f, "{} mod {}", value, modulus),
+            Overloads(definitions) => write!(f, "{}", definitions.iter().map(Object::to_string).collect::<Vec<_>>().join("; ")),
+            Composite(composite) => write!(f, "{}", composite),
             BuiltinFunction { parameters, .. } => write!(
                 f,
-                "fn({}) = <builtin-function>;",
+                "fn({}) = <builtin-function>",
                 parameters.iter().fold(String::new(), |mut acc, param| {
                     if !acc.is_empty() {
                         acc.push_str(", ");
@@ -368,12 +1239,29 @@ impl Eq for Object {}
 
 impl PartialOrd for Object {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        use Object::*;
         match (self, other) {
-            (Integer(left), Integer(right)) => left.partial_cmp(&right),
-            (Real(left), Integer(right)) => left.partial_cmp(&(*right as f64)),
-            (Integer(left), Real(right)) => left.partial_cmp(&(*right as i64)),
-            (Real(left), Real(right)) => left.partial_cmp(right),
+            (left, right) if left.fraction().is_some() && right.fraction().is_some() => Some(left.cmp(right)),
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => {
+                if left.as_real().unwrap().is_nan() || right.as_real().unwrap().is_nan() { None } else { Some(left.cmp(right)) }
+            },
+            // tuples are ordered lexicographically
+            (Object::Tuple { items }, Object::Tuple { items: items2 })
+            | (Object::Vector { items }, Object::Vector { items: items2 }) => items.iter().partial_cmp(items2.iter()),
+            (Object::Matrix { rows }, Object::Matrix { rows: rows2 }) => rows.iter().partial_cmp(rows2.iter()),
+            (Object::Atom { .. }, Object::Atom { .. }) | (Object::Boolean(_), Object::Boolean(_)) => Some(self.cmp(other)),
+            (Object::Undefined, _) | (_, Object::Undefined) => None,
+            (left, right) if left.is_function() && right.is_function() => Some(self.cmp(other)),
+            // quantities of different dimensions are never less, greater or equal
+            (Object::Quantity { magnitude, unit }, Object::Quantity { magnitude: magnitude2, unit: unit2 }) if unit.dimension == unit2.dimension => {
+                units::base_value(magnitude, unit).partial_cmp(&units::base_value(magnitude2, unit2))
+            },
+            (Object::Quantity { .. }, _) | (_, Object::Quantity { .. }) => None,
+            (Object::Enclosure(enclosure), Object::Enclosure(enclosure2)) => enclosure.partial_cmp(enclosure2),
+            // residues and complex numbers are not ordered
+            (Object::Residue { .. }, _) | (_, Object::Residue { .. }) => None,
+            (Object::Complex { .. }, _) | (_, Object::Complex { .. }) => None,
+            (Object::Graph { .. }, _) | (_, Object::Graph { .. }) => None,
+            (Object::Polynomial(_), _) | (_, Object::Polynomial(_)) => None,
             (left, right) => panic!("Cannot compare {} and {}.", left, right),
         }
     }
@@ -384,11 +1272,83 @@ impl Ord for Object {
         use Object::*;
         match (self, other) {
             (Integer(left), Integer(right)) => left.cmp(&right),
-            (Real(left), Real(right)) => Decimal::from_f64(*left).cmp(&Decimal::from_f64(*right)),
-            (Set { items, kind }, Set { items: items2, kind: kind2 }) if kind == kind2 => {
-                items.cmp(&items2)
+            (Decimal(left), Decimal(right)) => left.cmp(&right),
+            (Real(left), Real(right)) => (left + 0.0).total_cmp(&(right + 0.0)),
+            (left, right) if left.fraction().is_some() && right.fraction().is_some() => {
+                // denominators are positive so cross-multiplying preserves order
+                let ((n1, d1), (n2, d2)) = (left.fraction().unwrap(), right.fraction().unwrap());
+                (n1 * d2).cmp(&(n2 * d1))
+            },
+            // what is left are infinite floats, which are beyond every finite number however large
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => {
+                let real = |obj: &Object| match obj {
+                    Real(value) => *value,
+                    obj => obj.as_real().unwrap().clamp(f64::MIN, f64::MAX),
+                };
+                real(left).total_cmp(&real(right))
+            },
+            (Complex { real, imag }, Complex { real: real2, imag: imag2 }) => {
+                rust_decimal::Decimal::from_f64(*real).cmp(&rust_decimal::Decimal::from_f64(*real2))
+                    .then(rust_decimal::Decimal::from_f64(*imag).cmp(&rust_decimal::Decimal::from_f64(*imag2)))
+            },
+            (Set { items, .. }, Set { items: items2, .. }) => items.cmp(items2),
+            (Multiset { items, .. }, Multiset { items: items2, .. }) => items.cmp(items2),
+            (Tuple { items }, Tuple { items: items2 }) => items.cmp(items2),
+            (NumberSet(set), NumberSet(set2)) => set.cmp(set2),
+            (Intervals(intervals), Intervals(intervals2)) => intervals.cmp(intervals2),
+            // sets are ordered by their members in increasing order, which for ranges only depends on
+            // the first member, then the second, then how many there are
+            (Range { start, end, step }, Range { start: start2, end: end2, step: step2 }) => (start, step, end).cmp(&(start2, step2, end2)),
+            (Range { start, end, step }, Set { items, .. }) => Object::range_members(*start, *end, *step).cmp(items.iter().cloned()),
+            (Set { items, .. }, Range { start, end, step }) => items.iter().cloned().cmp(Object::range_members(*start, *end, *step)),
+            (SetBuilder { variable, parent_set, conditions, scope }, SetBuilder { variable: variable2, parent_set: parent_set2, conditions: conditions2, scope: scope2 }) => {
+                (variable, parent_set, format!("{:?}", conditions), scope.id()).cmp(&(variable2, parent_set2, format!("{:?}", conditions2), scope2.id()))
+            },
+            (Complement { universe, excluded }, Complement { universe: universe2, excluded: excluded2 }) => (universe, excluded).cmp(&(universe2, excluded2)),
+            // sets written in different ways are never equal, so they are ordered by how they were written
+            (left, right) if left.is_set() && right.is_set() => {
+                let rank = |set: &Object| match set {
+                    Set { .. } | Range { .. } => 0,
+                    SetBuilder { .. } => 1,
+                    NumberSet(_) => 2,
+                    Intervals(_) => 3,
+                    _ => 4,
+                };
+                rank(left).cmp(&rank(right))
+            },
+            (Vector { items }, Vector { items: items2 }) => items.cmp(items2),
+            (Matrix { rows }, Matrix { rows: rows2 }) => rows.cmp(rows2),
+            (Graph { adjacency, directed }, Graph { adjacency: adjacency2, directed: directed2 }) => {
+                (directed, adjacency).cmp(&(directed2, adjacency2))
             },
             (Boolean(left), Boolean(right)) => left.cmp(right),
+            (Polynomial(polynomial), Polynomial(polynomial2)) => polynomial.cmp(polynomial2),
+            (Enclosure(enclosure), Enclosure(enclosure2)) => enclosure.cmp(enclosure2),
+            // functions have no natural order, but sets of them need one; the same
+            // definition may have captured different values, so scopes are compared too
+            (Function { parameters, codomain, expression, scope }, Function { parameters: parameters2, codomain: codomain2, expression: expression2, scope: scope2 }) => {
+                (parameters, format!("{:?} {:?}", codomain, expression), scope.id()).cmp(&(parameters2, format!("{:?} {:?}", codomain2, expression2), scope2.id()))
+            },
+            (BuiltinFunction { parameters, body }, BuiltinFunction { parameters: parameters2, body: body2 }) => {
+                (parameters, *body as usize).cmp(&(parameters2, *body2 as usize))
+            },
+            (Overloads(definitions), Overloads(definitions2)) => definitions.cmp(definitions2),
+            (Composite(composite), Composite(composite2)) => composite.cmp(composite2),
+            // then functions, overloads, composites and builtins in that order
+            (left, right) if left.is_function() && right.is_function() => {
+                let rank = |function: &Object| match function {
+                    Function { .. } => 0,
+                    Overloads(_) => 1,
+                    Composite(_) => 2,
+                    _ => 3,
+                };
+                rank(left).cmp(&rank(right))
+            },
+            (Residue { value, modulus }, Residue { value: value2, modulus: modulus2 }) => (modulus, value).cmp(&(modulus2, value2)),
+            (Quantity { magnitude, unit }, Quantity { magnitude: magnitude2, unit: unit2 }) => {
+                (unit.dimension, units::base_value(magnitude, unit)).cmp(&(unit2.dimension, units::base_value(magnitude2, unit2)))
+            },
+            (Atom { name, index }, Atom { name: name2, index: index2 }) => (index, name).cmp(&(index2, name2)),
             (left, right) => panic!("Cannot compare {} and {}.", left, right),
         }
     }
@@ -399,11 +1359,33 @@ impl PartialEq for Object {
         use Object::*;
         match (self, rhs) {
             (Integer(left), Integer(right)) => left == right,
-            (Real(left), Real(right)) => left == right,
-            (Integer(left), Real(right)) => *left as f64 == *right,
-            (Real(left), Integer(right)) => *left == *right as f64,
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => left.cmp(right) == Ordering::Equal,
+            (left, right) if left.as_complex().is_some() && right.as_complex().is_some() => {
+                left.as_complex() == right.as_complex()
+            },
             (Boolean(left), Boolean(right)) => left == right,
-            (Undefined, _) => false,
+            (Atom { name, index }, Atom { name: name2, index: index2 }) => (index, name) == (index2, name2),
+            (Tuple { items }, Tuple { items: items2 }) => items == items2,
+            (Set { items, .. }, Set { items: items2, .. }) => items == items2,
+            (Multiset { items, .. }, Multiset { items: items2, .. }) => items == items2,
+            (NumberSet(set), NumberSet(set2)) => set == set2,
+            (Intervals(intervals), Intervals(intervals2)) => intervals == intervals2,
+            (Range { start, end, step }, Range { start: start2, end: end2, step: step2 }) => (start, end, step) == (start2, end2, step2),
+            (left, right) if left.is_set() && right.is_set() => left.cmp(right) == Ordering::Equal,
+            (Vector { items }, Vector { items: items2 }) => items == items2,
+            (Matrix { rows }, Matrix { rows: rows2 }) => rows == rows2,
+            (Graph { adjacency, directed }, Graph { adjacency: adjacency2, directed: directed2 }) => {
+                (directed, adjacency) == (directed2, adjacency2)
+            },
+            (Polynomial(polynomial), Polynomial(polynomial2)) => polynomial == polynomial2,
+            (left @ Quantity { .. }, right) | (left, right @ Quantity { .. }) => left.partial_cmp(right) == Some(Ordering::Equal),
+            (Enclosure(enclosure), Enclosure(enclosure2)) => enclosure == enclosure2,
+            (left, right) if left.is_function() && right.is_function() => left.cmp(right) == Ordering::Equal,
+            // integers are compared with residues after being reduced
+            (left @ Residue { .. }, right) | (left, right @ Residue { .. }) if residue::operands(left, right).is_some() => {
+                matches!(residue::operands(left, right).unwrap(), Ok((value, value2, _)) if value == value2)
+            },
+            (Undefined, _) | (_, Undefined) => false,
             (left, right) => panic!("Cannot equate {} and {}.", left, right),
         }
     }
@@ -414,10 +1396,43 @@ impl Add for Object {
     fn add(self, rhs: Self) -> Self::Output {
         use Object::*;
         match (self, rhs) {
-            (Integer(left), Integer(right)) => Ok(Integer(left + right)),
-            (Integer(left), Real(right)) => Ok(Real(left as f64 + right)),
-            (Real(left), Integer(right)) => Ok(Real(left + right as f64)),
-            (Real(left), Real(right)) => Ok(Real(left + right)),
+            (Integer(left), Integer(right)) => {
+                Ok(left.checked_add(right).map_or_else(|| Object::integer(BigInt::from(left) + right), Integer))
+            },
+            (left, right) if left.is_exact() && right.is_exact() => {
+                let ((n1, d1), (n2, d2)) = (left.fraction().unwrap(), right.fraction().unwrap());
+                Ok(Object::rational(n1 * &d2 + n2 * &d1, d1 * d2))
+            },
+            (left, right) if left.as_decimal().is_some() && right.as_decimal().is_some() => {
+                Ok(left.decimal_operation(&right, rust_decimal::Decimal::checked_add, |left, right| left + right))
+            },
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => {
+                Ok(Real(left.as_real().unwrap() + right.as_real().unwrap()))
+            },
+            (left, right) if left.as_complex().is_some() && right.as_complex().is_some() => {
+                let ((a, b), (c, d)) = (left.as_complex().unwrap(), right.as_complex().unwrap());
+                Ok(Object::complex(a + c, b + d))
+            },
+            (left @ Vector { .. }, right @ Vector { .. })
+            | (left @ Matrix { .. }, right @ Matrix { .. }) => left.zip_entries(&right, Object::add),
+            // the sum of multisets adds the multiplicities
+            (left @ Multiset { .. }, right) | (left, right @ Multiset { .. }) => {
+                left.multiset_operation(&right, "sum", |left, right| left + right).unwrap()
+            },
+            (left @ Polynomial(_), right) | (left, right @ Polynomial(_)) if polynomial::Polynomial::operands(&left, &right).is_some() => {
+                let (left, right) = polynomial::Polynomial::operands(&left, &right).unwrap();
+                Ok(Polynomial(left.add(&right)?))
+            },
+            (left, right) if composite::operands(&left, &right) => composite::pointwise(Token::Plus, left, right),
+            (left, right) if units::operands(&left, &right) => units::add(&left, &right, false),
+            (left, right) if enclosure::operands(&left, &right).is_some() => {
+                let (left, right) = enclosure::operands(&left, &right).unwrap();
+                Ok(Enclosure(left.add(&right)))
+            },
+            (left, right) if residue::operands(&left, &right).is_some() => {
+                let (left, right, modulus) = residue::operands(&left, &right).unwrap()?;
+                Ok(residue::add(left, right, modulus))
+            },
             (left, right) => Err(RuntimeError::OperatorError(format!("Cannot add {} and {}.", left, right))),
         }
     }
@@ -428,10 +1443,53 @@ impl Mul for Object {
     fn mul(self, rhs: Self) -> Self::Output {
         use Object::*;
         match (self, rhs) {
-            (Integer(left), Integer(right)) => Ok(Integer(left * right)),
-            (Integer(left), Real(right)) => Ok(Real(left as f64 * right)),
-            (Real(left), Integer(right)) => Ok(Real(left * right as f64)),
-            (Real(left), Real(right)) => Ok(Real(left * right)),
+            (Integer(left), Integer(right)) => {
+                Ok(left.checked_mul(right).map_or_else(|| Object::integer(BigInt::from(left) * right), Integer))
+            },
+            (left, right) if left.is_exact() && right.is_exact() => {
+                let ((n1, d1), (n2, d2)) = (left.fraction().unwrap(), right.fraction().unwrap());
+                Ok(Object::rational(n1 * n2, d1 * d2))
+            },
+            (left, right) if left.as_decimal().is_some() && right.as_decimal().is_some() => {
+                Ok(left.decimal_operation(&right, rust_decimal::Decimal::checked_mul, |left, right| left * right))
+            },
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => {
+                Ok(Real(left.as_real().unwrap() * right.as_real().unwrap()))
+            },
+            (left, right) if left.as_complex().is_some() && right.as_complex().is_some() => {
+                let ((a, b), (c, d)) = (left.as_complex().unwrap(), right.as_complex().unwrap());
+                Ok(Object::complex(a * c - b * d, a * d + b * c))
+            },
+            (Vector { items }, Vector { items: items2 }) => linear_algebra::dot(&items, &items2),
+            (Matrix { rows }, Matrix { rows: rows2 }) if rows.first().map_or(0, Vec::len) == rows2.len() => {
+                Ok(Matrix { rows: linear_algebra::product(&rows, &rows2)? })
+            },
+            (Matrix { rows }, Vector { items }) if rows.first().map_or(0, Vec::len) == items.len() => Ok(Vector {
+                items: rows.iter().map(|row| linear_algebra::dot(row, &items)).collect::<Result<_, _>>()?
+            }),
+            (Vector { items }, Matrix { rows }) if items.len() == rows.len() => Ok(Vector {
+                items: linear_algebra::transpose(&rows).iter().map(|column| linear_algebra::dot(&items, column)).collect::<Result<_, _>>()?
+            }),
+            (scalar, array @ Vector { .. })
+            | (scalar, array @ Matrix { .. })
+            | (array @ Vector { .. }, scalar)
+            | (array @ Matrix { .. }, scalar) if scalar.is_number() => {
+                array.map_entries(&|entry| scalar.clone() * entry)
+            },
+            (left @ Polynomial(_), right) | (left, right @ Polynomial(_)) if polynomial::Polynomial::operands(&left, &right).is_some() => {
+                let (left, right) = polynomial::Polynomial::operands(&left, &right).unwrap();
+                Ok(Polynomial(left.mul(&right)?))
+            },
+            (left, right) if composite::operands(&left, &right) => composite::pointwise(Token::Multiply, left, right),
+            (left, right) if units::operands(&left, &right) => units::multiply(&left, &right, 1),
+            (left, right) if enclosure::operands(&left, &right).is_some() => {
+                let (left, right) = enclosure::operands(&left, &right).unwrap();
+                Ok(Enclosure(left.mul(&right)))
+            },
+            (left, right) if residue::operands(&left, &right).is_some() => {
+                let (left, right, modulus) = residue::operands(&left, &right).unwrap()?;
+                Ok(residue::mul(left, right, modulus))
+            },
             (left, right) => Err(RuntimeError::OperatorError(format!("Cannot multiply {} and {}.", left, right))),
         }
     }
@@ -442,10 +1500,39 @@ impl Sub for Object {
     fn sub(self, rhs: Self) -> Self::Output {
         use Object::*;
         match (self, rhs) {
-            (Integer(left), Integer(right)) => Ok(Integer(left - right)),
-            (Integer(left), Real(right)) => Ok(Real(left as f64 - right)),
-            (Real(left), Integer(right)) => Ok(Real(left - right as f64)),
-            (Real(left), Real(right)) => Ok(Real(left - right)),
+            (Integer(left), Integer(right)) => {
+                Ok(left.checked_sub(right).map_or_else(|| Object::integer(BigInt::from(left) - right), Integer))
+            },
+            (left, right) if left.is_exact() && right.is_exact() => {
+                let ((n1, d1), (n2, d2)) = (left.fraction().unwrap(), right.fraction().unwrap());
+                Ok(Object::rational(n1 * &d2 - n2 * &d1, d1 * d2))
+            },
+            (left, right) if left.as_decimal().is_some() && right.as_decimal().is_some() => {
+                Ok(left.decimal_operation(&right, rust_decimal::Decimal::checked_sub, |left, right| left - right))
+            },
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => {
+                Ok(Real(left.as_real().unwrap() - right.as_real().unwrap()))
+            },
+            (left, right) if left.as_complex().is_some() && right.as_complex().is_some() => {
+                let ((a, b), (c, d)) = (left.as_complex().unwrap(), right.as_complex().unwrap());
+                Ok(Object::complex(a - c, b - d))
+            },
+            (left @ Vector { .. }, right @ Vector { .. })
+            | (left @ Matrix { .. }, right @ Matrix { .. }) => left.zip_entries(&right, Object::sub),
+            (left @ Polynomial(_), right) | (left, right @ Polynomial(_)) if polynomial::Polynomial::operands(&left, &right).is_some() => {
+                let (left, right) = polynomial::Polynomial::operands(&left, &right).unwrap();
+                Ok(Polynomial(left.sub(&right)?))
+            },
+            (left, right) if composite::operands(&left, &right) => composite::pointwise(Token::Minus, left, right),
+            (left, right) if units::operands(&left, &right) => units::add(&left, &right, true),
+            (left, right) if enclosure::operands(&left, &right).is_some() => {
+                let (left, right) = enclosure::operands(&left, &right).unwrap();
+                Ok(Enclosure(left.sub(&right)))
+            },
+            (left, right) if residue::operands(&left, &right).is_some() => {
+                let (left, right, modulus) = residue::operands(&left, &right).unwrap()?;
+                Ok(residue::sub(left, right, modulus))
+            },
             (left, right) => Err(RuntimeError::OperatorError(format!("Cannot subtract {} and {}.", left, right))),
         }
     }
@@ -456,37 +1543,64 @@ impl Div for Object {
     fn div(self, rhs: Self) -> Self::Output {
         use Object::*;
         match (self, rhs) {
-            (Integer(left), Integer(right)) => {
-                if right == 0 {
+            (left, right) if left.is_exact() && right.is_exact() => {
+                let ((n1, d1), (n2, d2)) = (left.fraction().unwrap(), right.fraction().unwrap());
+                Ok(Object::rational(n1 * d2, d1 * n2))
+            },
+            (left, right) if left.as_decimal().is_some() && right.as_decimal().is_some() => {
+                if right.as_decimal().unwrap().is_zero() {
                     Ok(Undefined)
                 } else {
-                    Ok(Real(left as f64 / right as f64))
+                    Ok(left.decimal_operation(
+                        &right,
+                        |left, right| left.checked_div(right).map(|value| value.round_dp_with_strategy(DECIMAL_PLACES, RoundingStrategy::MidpointNearestEven)),
+                        |left, right| left / right
+                    ))
                 }
-            }
-            (Integer(left), Real(right)) => {
-                let value = left as f64 / right;
+            },
+            (left, right) if left.as_real().is_some() && right.as_real().is_some() => {
+                let value = left.as_real().unwrap() / right.as_real().unwrap();
                 if value.is_nan() || value.is_infinite() {
                     Ok(Undefined)
                 } else {
                     Ok(Real(value))
                 }
-            }
-            (Real(left), Integer(right)) => {
-                let value = left / right as f64;
-                if value.is_nan() || value.is_infinite() {
+            },
+            (left, right) if left.as_complex().is_some() && right.as_complex().is_some() => {
+                let ((a, b), (c, d)) = (left.as_complex().unwrap(), right.as_complex().unwrap());
+                let denominator = c * c + d * d;
+                if denominator == 0.0 {
                     Ok(Undefined)
                 } else {
-                    Ok(Real(value))
+                    Ok(Object::complex((a * c + b * d) / denominator, (b * c - a * d) / denominator))
                 }
-            }
-            (Real(left), Real(right)) => {
-                let value = left / right;
-                if value.is_nan() || value.is_infinite() {
-                    Ok(Undefined)
-                } else {
-                    Ok(Real(value))
+            },
+            (array @ Vector { .. }, scalar)
+            | (array @ Matrix { .. }, scalar) if scalar.is_number() => {
+                array.map_entries(&|entry| entry / scalar.clone())
+            },
+            // only exact division gives a polynomial; see the `divide` builtin for the remainder
+            (left @ Polynomial(_), right) | (left, right @ Polynomial(_)) if polynomial::Polynomial::operands(&left, &right).is_some() => {
+                let (dividend, divisor) = polynomial::Polynomial::operands(&left, &right).unwrap();
+                if divisor.coefficients.is_empty() {
+                    return Ok(Undefined)
+                };
+                match dividend.divide(&divisor)? {
+                    (quotient, remainder) if remainder.coefficients.is_empty() => Ok(Polynomial(quotient)),
+                    _ => Err(RuntimeError::OperatorError(format!("Cannot divide {} by {} exactly.", left, right))),
                 }
-            }
+            },
+            (left, right) if composite::operands(&left, &right) => composite::pointwise(Token::Division, left, right),
+            (left, right) if units::operands(&left, &right) => units::multiply(&left, &right, -1),
+            // undefined when the divisor might be zero
+            (left, right) if enclosure::operands(&left, &right).is_some() => {
+                let (left, right) = enclosure::operands(&left, &right).unwrap();
+                Ok(left.div(&right).map_or(Undefined, Enclosure))
+            },
+            (left, right) if residue::operands(&left, &right).is_some() => {
+                let (left, right, modulus) = residue::operands(&left, &right).unwrap()?;
+                Ok(residue::div(left, right, modulus))
+            },
             (left, right) => Err(RuntimeError::OperatorError(format!("Cannot divide {} and {}.", left, right))),
         }
     }
@@ -497,8 +1611,19 @@ impl Neg for Object {
     fn neg(self) -> Self::Output {
         use Object::*;
         match self {
-            Integer(value) => Ok(Integer(-value)),
+            Integer(value) => Ok(value.checked_neg().map_or_else(|| Object::integer(-BigInt::from(value)), Integer)),
+            BigInteger(value) => Ok(Object::integer(-value)),
+            Rational { numerator, denominator } => Ok(Object::rational(-BigInt::from(numerator), BigInt::from(denominator))),
+            BigRational { numerator, denominator } => Ok(Object::rational(-numerator, denominator)),
             Real(value) => Ok(Real(-value)),
+            Decimal(value) => Ok(Decimal(-value)),
+            Complex { real, imag } => Ok(Complex { real: -real, imag: -imag }),
+            array @ Vector { .. } | array @ Matrix { .. } => array.map_entries(&Object::neg),
+            Polynomial(polynomial) => Ok(Polynomial(polynomial.scale(&Integer(-1))?)),
+            Quantity { magnitude, unit } => units::quantity((*magnitude).neg()?, unit),
+            Enclosure(enclosure) => Ok(Enclosure(enclosure.neg())),
+            Residue { value, modulus } => Ok(residue::sub(0, value, modulus)),
+            function if function.is_function() => composite::pointwise(Token::Multiply, Integer(-1), function),
             obj => Err(RuntimeError::OperatorError(format!("Cannot negate {}.", obj))),
         }
     }
@@ -20,6 +20,7 @@ impl Operator {
             // prefix operations
             Self(Token::Not, Location::Prefix)
             | Self(Token::Minus, Location::Prefix)
+            | Self(Token::SetDifference, Location::Prefix)
 
             | Self(Token::And, Location::Infix)
             | Self(Token::Or, Location::Infix)
@@ -40,14 +41,20 @@ impl Operator {
             | Self(Token::Division, Location::Infix)
             | Self(Token::Modulo, Location::Infix)
             | Self(Token::As, Location::Infix)
+            | Self(Token::Mod, Location::Infix)
+            | Self(Token::ModClause, Location::Infix)
+            | Self(Token::Congruent, Location::Infix)
+            | Self(Token::Compose, Location::Infix)
             | Self(Token::Exponent, Location::Infix)
             | Self(Token::LeftParen, Location::Infix)
+            | Self(Token::LeftSquareBracket, Location::Infix)
             | Self(Token::Union, Location::Infix)
             | Self(Token::Intersection, Location::Infix)
             | Self(Token::SymmetricDifference, Location::Infix)
             | Self(Token::SetDifference, Location::Infix) 
             
-            | Self(Token::Bang, Location::Postfix) => true,
+            | Self(Token::Bang, Location::Postfix)
+            | Self(Token::Imaginary, Location::Postfix) => true,
 
             _ => false
         }
@@ -73,7 +80,8 @@ impl Operator {
         match self {
             // prefix operations
             Self(Token::Not, Location::Prefix) => Ok(Precedence::Not),
-            Self(Token::Minus, Location::Prefix) => Ok(Precedence::Negative),
+            Self(Token::Minus, Location::Prefix)
+            | Self(Token::SetDifference, Location::Prefix) => Ok(Precedence::Negative),
 
             // infix operations
             Self(Token::And, Location::Infix)
@@ -86,6 +94,7 @@ impl Operator {
             | Self(Token::GreaterThanEquals, Location::Infix)
             | Self(Token::Equals, Location::Infix)
             | Self(Token::NotEquals, Location::Infix)
+            | Self(Token::Congruent, Location::Infix)
             | Self(Token::Question, Location::Infix) => Ok(Precedence::LessGreaterEqualCoa),
 
             Self(Token::In, Location::Infix) => Ok(Precedence::In),
@@ -97,13 +106,17 @@ impl Operator {
 
             Self(Token::Multiply, Location::Infix)
             | Self(Token::Division, Location::Infix)
-            | Self(Token::Modulo, Location::Infix) => Ok(Precedence::MultDivMod),
+            | Self(Token::Modulo, Location::Infix)
+            | Self(Token::Mod, Location::Infix)
+            | Self(Token::Compose, Location::Infix) => Ok(Precedence::MultDivMod),
 
-            Self(Token::As, Location::Infix) => Ok(Precedence::As),
+            Self(Token::As, Location::Infix)
+            | Self(Token::ModClause, Location::Infix) => Ok(Precedence::As),
 
             Self(Token::Exponent, Location::Infix) => Ok(Precedence::Exponent),
 
-            Self(Token::LeftParen, Location::Infix) => Ok(Precedence::Call),
+            Self(Token::LeftParen, Location::Infix)
+            | Self(Token::LeftSquareBracket, Location::Infix) => Ok(Precedence::Call),
 
             // set infix operations
             Self(Token::Union, Location::Infix)
@@ -112,7 +125,8 @@ impl Operator {
             | Self(Token::SetDifference, Location::Infix) => Ok(Precedence::Exponent),
 
             // postfix operations
-            Self(Token::Bang, Location::Postfix) => Ok(Precedence::Postfix),
+            Self(Token::Bang, Location::Postfix)
+            | Self(Token::Imaginary, Location::Postfix) => Ok(Precedence::Postfix),
 
             Self(token, loc) => Err(SyntaxError(format!("Precedence for token {} and location {} is not defined.", token, loc)))
         }
@@ -1,13 +1,34 @@
+use super::expression::Expression;
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter, self};
 use std::hash::Hash;
 
 #[derive(Debug, PartialEq, Clone, Hash)]
 pub struct Parameter {
     pub name: String,
+    pub domain: Option<Expression>,  // the set arguments must belong to, from `x: R` or `x in R`
+}
+
+impl Eq for Parameter {}
+
+impl PartialOrd for Parameter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// expressions have no order of their own, so domains are ordered by how they are written
+impl Ord for Parameter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.name, format!("{:?}", self.domain)).cmp(&(&other.name, format!("{:?}", other.domain)))
+    }
 }
 
 impl Display for Parameter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.name)
+        match &self.domain {
+            Some(domain) => write!(f, "{}: {}", self.name, domain),
+            None => write!(f, "{}", self.name),
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,6 @@
 use super::errors::SyntaxError;
 use super::parameter::Parameter;
+use super::generator::Generator;
 use std::iter::Peekable;
 use super::statement::Statement;
 use crate::interpreter::token::Token;
@@ -37,6 +38,7 @@ impl<I: Iterator<Item=Token>> Parser<I> {
         match self.iterator.peek() {
             Some(Let) => self.parse_assignment_statement().map(Some),
             Some(FuncFn) => self.parse_function_declaration().map(Some),
+            Some(Import) => self.parse_import_statement().map(Some),
             Some(Eof) | None => Ok(None),
             _ => self.parse_expression_statement().map(Some),
         }
@@ -47,29 +49,61 @@ impl<I: Iterator<Item=Token>> Parser<I> {
             Token::Undefined => Ok(Expression::UndefinedLiteral),
             Token::Identifier(name) => Ok(Expression::Identifier(name)),
             Token::Integer(value) => Ok(Expression::IntegerLiteral(value)),
+            Token::BigInteger(value) => Ok(Expression::BigIntegerLiteral(value)),
             Token::Real(value) => Ok(Expression::RealLiteral(value)),
             Token::True => Ok(Expression::BooleanLiteral(true)),
+            Token::FuncFn => self.parse_function_literal(),
             Token::False => Ok(Expression::BooleanLiteral(false)),
-            t @ Token::Not | t @ Token::Minus => {
+            t @ Token::Not | t @ Token::Minus | t @ Token::SetDifference => {
                 let op = Operator(t, Location::Prefix);
                 let expr_result = self.parse_expression(op.precedence()?);
                 match expr_result {
                     Ok(expr) => Ok(Expression::Combination {
                         left: None,
-                        operator: op,
                         right: Some(Box::new(expr)),
+                        operator: op,
                     }),
                     Err(value) => Err(value),
                 }
             },
             Token::LeftParen => {
+                if self.iterator.next_if_eq(&Token::RightParen).is_some() {
+                    return Ok(Expression::TupleLiteral(vec![]))
+                };
                 let expr = self.parse_expression(Precedence::Lowest)?;
-                if let Some(_) = self.iterator.next_if(|token| token == &Token::RightParen) {
+                if self.iterator.next_if_eq(&Token::DoubleDot).is_some() {
+                    self.parse_interval(expr, false)
+                } else if let Some(_) = self.iterator.next_if(|token| token == &Token::RightParen) {
                     Ok(expr)
+                } else if self.iterator.next_if_eq(&Token::Comma).is_some() {
+                    // a comma makes this a tuple rather than a grouped expression
+                    let mut expressions = vec![expr];
+                    if self.iterator.peek() != Some(&Token::RightParen) {
+                        let second_expression = self.parse_expression(Precedence::Lowest)?;
+                        // `(a, b]` is a half-open interval rather than a tuple
+                        if self.iterator.next_if_eq(&Token::RightSquareBracket).is_some() {
+                            return Ok(Expression::IntervalLiteral {
+                                start: Box::new(expressions.remove(0)),
+                                end: Box::new(second_expression),
+                                start_closed: false,
+                                end_closed: true,
+                            })
+                        };
+                        expressions.push(second_expression);
+                        if self.iterator.next_if_eq(&Token::Comma).is_none() && self.iterator.peek() != Some(&Token::RightParen) {
+                            return Err("Missing comma after parameter in tuple literal expression.".into())
+                        };
+                    };
+                    expressions.extend(self.parse_expression_list(Token::RightParen, "tuple literal expression")?);
+                    Ok(Expression::TupleLiteral(expressions))
                 } else {
                     Err("Missing right parenthesis after grouped expression.".into())
                 }
             },
+            Token::LeftSquareBracket => self.parse_vector_expression(),
+            Token::LeftMultisetBrace => {
+                Ok(Expression::MultisetLiteral(self.parse_expression_list(Token::RightMultisetBrace, "multiset literal expression")?))
+            },
             Token::Bar => {
                 let expr = self.parse_expression(Precedence::Lowest)?;
                 if let Some(_) = self.iterator.next_if(|token| token == &Token::Bar) {
@@ -82,20 +116,188 @@ impl<I: Iterator<Item=Token>> Parser<I> {
         }
     }
 
-    fn parse_set_expression(&mut self, first_expression: Expression) -> Result<Expression, SyntaxError> {
-        let mut expressions = vec![first_expression];
+    // comma separated expressions up to and including `terminator`; a trailing comma is allowed
+    fn parse_expression_list(&mut self, terminator: Token, context: &str) -> Result<Vec<Expression>, SyntaxError> {
+        let mut expressions = vec![];
         loop {
-            if let Some(_) = self.iterator.next_if_eq(&Token::RightBrace) {
-                break                 
+            if self.iterator.next_if_eq(&terminator).is_some() {
+                break Ok(expressions)
+            };
+            expressions.push(self.parse_expression(Precedence::Lowest)?);
+            if self.iterator.next_if_eq(&Token::Comma).is_none() && self.iterator.peek() != Some(&terminator) {
+                break Err(format!("Missing comma after parameter in {}.", context).into())
+            };
+        }
+    }
+
+    // `<identifier> in <set>`, which parses as an ordinary containment expression
+    fn generator_from(expression: Expression, context: &str) -> Result<Generator, SyntaxError> {
+        match expression {
+            Expression::Combination {
+                left: Some(left),
+                operator: Operator(Token::In, Location::Infix),
+                right: Some(set),
+            } => match *left {
+                Expression::Identifier(variable) => Ok(Generator { variable, set: *set }),
+                left => Err(format!("Expected an identifier before `in` in {}, got {}.", context, left).into()),
+            },
+            expr => Err(format!("Expected `<identifier> in <set>` in {}, got {}.", context, expr).into()),
+        }
+    }
+
+    // `<identifier> in <set>, ...` up to and including `terminator`
+    fn parse_generators(&mut self, terminator: Token, context: &str) -> Result<Vec<Generator>, SyntaxError> {
+        let generators = self.parse_expression_list(terminator, context)?
+            .into_iter()
+            .map(|expr| Self::generator_from(expr, context))
+            .collect::<Result<Vec<_>, SyntaxError>>()?;
+        if generators.is_empty() {
+            Err(format!("Missing generators in {}.", context).into())
+        } else {
+            Ok(generators)
+        }
+    }
+
+    // the rest of `[a .. b]` or `(a .. b)` once `..` has been eaten, where either end may be open
+    fn parse_interval(&mut self, start: Expression, start_closed: bool) -> Result<Expression, SyntaxError> {
+        let end = self.parse_expression(Precedence::Lowest)?;
+        let end_closed = match self.iterator.next_if(|token| token == &Token::RightSquareBracket || token == &Token::RightParen) {
+            Some(token) => token == Token::RightSquareBracket,
+            None => return Err("Missing right bracket or parenthesis after interval.".into()),
+        };
+        Ok(Expression::IntervalLiteral {
+            start: Box::new(start),
+            end: Box::new(end),
+            start_closed,
+            end_closed,
+        })
+    }
+
+    fn parse_vector_expression(&mut self) -> Result<Expression, SyntaxError> {
+        // left square bracket has already been eaten
+        if self.iterator.next_if_eq(&Token::RightSquareBracket).is_some() {
+            return Ok(Expression::VectorLiteral(vec![]))
+        };
+        let first_expression = self.parse_expression(Precedence::Lowest)?;
+        if self.iterator.next_if_eq(&Token::DoubleDot).is_some() {
+            return self.parse_interval(first_expression, true)
+        };
+
+        if self.iterator.next_if_eq(&Token::Where).is_some() {
+            let generators = self.parse_generators(Token::RightSquareBracket, "matrix builder")?;
+            return if generators.len() > 2 {
+                Err("Matrix builders take at most two generators.".into())
             } else {
-                match self.iterator.next() {
-                    Some(Token::Comma) => (),
-                    _ => return Err("Missing comma after parameter in set literal expression.".into())
-                };
-                expressions.push(self.parse_expression(Precedence::Lowest)?);
+                Ok(Expression::MatrixBuilder {
+                    expression: Box::new(first_expression),
+                    generators
+                })
             }
         };
-        Ok(Expression::SetLiteral(expressions))
+
+        let mut expressions = vec![first_expression];
+        if self.iterator.next_if_eq(&Token::Comma).is_some() && self.iterator.peek() != Some(&Token::RightSquareBracket) {
+            let second_expression = self.parse_expression(Precedence::Lowest)?;
+            // `[a, b)` is a half-open interval rather than a vector
+            if self.iterator.next_if_eq(&Token::RightParen).is_some() {
+                return Ok(Expression::IntervalLiteral {
+                    start: Box::new(expressions.remove(0)),
+                    end: Box::new(second_expression),
+                    start_closed: true,
+                    end_closed: false,
+                })
+            };
+            expressions.push(second_expression);
+            if self.iterator.next_if_eq(&Token::Comma).is_none() && self.iterator.peek() != Some(&Token::RightSquareBracket) {
+                return Err("Missing comma after parameter in vector literal expression.".into())
+            };
+        };
+        expressions.extend(self.parse_expression_list(Token::RightSquareBracket, "vector literal expression")?);
+
+        // a vector of vectors is a matrix
+        if expressions.iter().all(|expr| matches!(expr, Expression::VectorLiteral(_))) {
+            let rows = expressions.into_iter().map(|expr| match expr {
+                Expression::VectorLiteral(row) => row,
+                _ => unreachable!()
+            }).collect::<Vec<_>>();
+            if rows.iter().any(|row| row.len() != rows[0].len()) {
+                Err("Matrix rows must all have the same length.".into())
+            } else {
+                Ok(Expression::MatrixLiteral(rows))
+            }
+        } else {
+            Ok(Expression::VectorLiteral(expressions))
+        }
+    }
+
+    fn parse_set_builder_expression(&mut self, first_expression: Expression) -> Result<Expression, SyntaxError> {
+        // eat colon
+        self.iterator.next();
+        let Generator { variable, set } = Self::generator_from(first_expression, "set builder")?;
+        let conditions = self.parse_expression_list(Token::RightBrace, "set builder")?;
+        if conditions.is_empty() {
+            Err("Missing conditions after `:` in set builder.".into())
+        } else {
+            Ok(Expression::SetBuilder {
+                variable,
+                parent_set: Box::new(set),
+                conditions
+            })
+        }
+    }
+
+    // assigning a set of nothing but bare names may declare an enumeration, which is decided once the names are looked up
+    fn enumeration(expression: Expression) -> Expression {
+        match expression {
+            Expression::SetLiteral(expressions) if expressions.iter().all(|expr| matches!(expr, Expression::Identifier(_))) => {
+                Expression::Enumeration(expressions.into_iter().map(|expr| match expr {
+                    Expression::Identifier(name) => name,
+                    _ => unreachable!(),
+                }).collect())
+            },
+            expression => expression,
+        }
+    }
+
+    fn parse_set_expression(&mut self, first_expression: Expression) -> Result<Expression, SyntaxError> {
+        let mut expressions = vec![first_expression];
+        loop {
+            if self.iterator.next_if_eq(&Token::RightBrace).is_some() {
+                break Ok(Expression::SetLiteral(expressions))
+            };
+            if self.iterator.next_if_eq(&Token::Comma).is_none() {
+                break Err("Missing comma after parameter in set literal expression.".into())
+            };
+            if self.iterator.next_if_eq(&Token::Ellipsis).is_some() {
+                break self.parse_range_expression(expressions)
+            };
+            if self.iterator.next_if_eq(&Token::RightBrace).is_some() {
+                break Ok(Expression::SetLiteral(expressions))
+            };
+            expressions.push(self.parse_expression(Precedence::Lowest)?);
+        }
+    }
+
+    fn parse_range_expression(&mut self, expressions: Vec<Expression>) -> Result<Expression, SyntaxError> {
+        // ellipsis has already been eaten
+        let mut expressions = expressions.into_iter().map(Box::new);
+        let (first, second) = match (expressions.next(), expressions.next(), expressions.next()) {
+            (Some(first), second, None) => (first, second),
+            _ => return Err("Expected one or two members before `...` in range expression.".into()),
+        };
+        if self.iterator.next_if_eq(&Token::Comma).is_none() {
+            return Err("Missing comma after `...` in range expression.".into())
+        };
+        let last = Box::new(self.parse_expression(Precedence::Lowest)?);
+        self.iterator.next_if_eq(&Token::Comma);
+        if self.iterator.next_if_eq(&Token::RightBrace).is_none() {
+            return Err("Expected `}` after the last member of range expression.".into())
+        };
+        Ok(Expression::RangeLiteral {
+            first,
+            second,
+            last
+        })
     }
 
     fn parse_expression_with_brace(&mut self) -> Result<Expression, SyntaxError> {
@@ -112,7 +314,16 @@ impl<I: Iterator<Item=Token>> Parser<I> {
                 Some(Token::If) | Some(Token::Else) => self.parse_piecewise_block_expression(first_expression),
                 Some(Token::Comma) => self.parse_set_expression(first_expression),
                 Some(Token::RightBrace) => self.parse_set_expression(first_expression),
-                Some(_) => Err("Expected `,` or `if` or `else` after expression.".into()),
+                Some(Token::Colon) => self.parse_set_builder_expression(first_expression),
+                Some(Token::For) => {
+                    // eat for
+                    self.iterator.next();
+                    Ok(Expression::SetComprehension {
+                        expression: Box::new(first_expression),
+                        generators: self.parse_generators(Token::RightBrace, "set comprehension")?
+                    })
+                },
+                Some(_) => Err("Expected `,`, `:`, `for`, `if` or `else` after expression.".into()),
                 None => Err("Unexpected end to token stream.".into())
             }
         }
@@ -194,61 +405,90 @@ impl<I: Iterator<Item=Token>> Parser<I> {
         let precedence = operator.precedence()?;
 
         let expression = if operator == Operator(Token::LeftParen, Location::Infix) {
-            let mut arguments = vec![];
-            match self.iterator.peek() {
-                Some(Token::RightParen) => {
-                    self.iterator.next().map(drop);
-                    return Ok(Expression::Call {
-                        function: Box::new(left),
-                        arguments
-                    })
-                },
-                _ => arguments.push(self.parse_expression(Precedence::Lowest)?)
+            Expression::Call {
+                function: Box::new(left),
+                arguments: self.parse_expression_list(Token::RightParen, "call expression")?
+            }
+        } else if operator == Operator(Token::ModClause, Location::Infix) {
+            // `b (mod n)` means the same as `b mod n`
+            let modulus = self.parse_expression(Precedence::Lowest)?;
+            if self.iterator.next_if_eq(&Token::RightParen).is_none() {
+                return Err("Missing right paren after modulus.".into())
             };
-
-            loop {
-                if let Some(_) = self.iterator.next_if_eq(&Token::RightParen) {
-                    break Expression::Call {
-                        function: Box::new(left),
-                        arguments
-                    }                    
-                } else {
-                    match self.iterator.next() {
-                        Some(Token::Comma) => (),
-                        _ => return Err("Missing comma after parameter in call expression.".into())
-                    };
-                    arguments.push(self.parse_expression(Precedence::Lowest)?);
-                }
+            Expression::Combination {
+                left: Some(Box::new(left)),
+                operator: Operator(Token::Mod, Location::Infix),
+                right: Some(Box::new(modulus)),
+            }
+        } else if operator == Operator(Token::LeftSquareBracket, Location::Infix) {
+            let index = self.parse_expression(Precedence::Lowest)?;
+            if self.iterator.next_if_eq(&Token::RightSquareBracket).is_none() {
+                return Err("Missing right square bracket after index.".into())
+            };
+            Expression::Index {
+                collection: Box::new(left),
+                index: Box::new(index)
             }
         } else {
-            let left = Some(Box::new(left));
-            let right = match self.parse_expression(precedence) {
-                Ok(right) => Ok(Some(Box::new(right))),
-                Err(value) => Err(value)
-            }?;
-            
+            let right = self.parse_expression(precedence)?;
             Expression::Combination {
-                left,
+                left: Some(Box::new(left)),
                 operator,
-                right
+                right: Some(Box::new(right)),
             }
         };
         Ok(expression)
     }
 
     fn parse_postfix_expression(&mut self, expression: Expression) -> Result<Expression, SyntaxError> {
-        // eat ! token
-        if self.iterator.next_if_eq(&Token::Bang).is_none() {
-            return Err("Expected ! as a postfix operator.".into())
-        }
+        // eat ! or i token
+        let token = match self.iterator.next_if(|token| token == &Token::Bang || token == &Token::Imaginary) {
+            Some(token) => token,
+            None => return Err("Expected ! or i as a postfix operator.".into())
+        };
 
         Ok(Expression::Combination {
             left: Some(Box::new(expression)),
-            operator: Operator(Token::Bang, Location::Postfix),
+            operator: Operator(token, Location::Postfix),
             right: None,
         })
     }
 
+    // the parameters before an arrow: a name, or a tuple of names possibly with domains (`x in R`)
+    fn parameters_from(expression: Expression) -> Result<Vec<Parameter>, SyntaxError> {
+        let names = match expression {
+            Expression::TupleLiteral(items) => items,
+            name @ Expression::Identifier(_) => vec![name],
+            domain @ Expression::Combination { operator: Operator(Token::In, Location::Infix), .. } => vec![domain],
+            expr => return Err(format!("Expected parameter names before arrow, got {}.", expr).into()),
+        };
+        names.into_iter().map(|name| match name {
+            Expression::Identifier(name) => Ok(Parameter { name, domain: None }),
+            Expression::Combination {
+                left: Some(left),
+                operator: Operator(Token::In, Location::Infix),
+                right: Some(domain),
+            } => match *left {
+                Expression::Identifier(name) => Ok(Parameter { name, domain: Some(*domain) }),
+                expr => Err(format!("Expected a parameter name before arrow, got {}.", expr).into()),
+            },
+            expr => Err(format!("Expected a parameter name before arrow, got {}.", expr).into()),
+        }).collect()
+    }
+
+    // an integer or real literal, possibly negated
+    fn is_number_literal(expression: &Expression) -> bool {
+        match expression {
+            Expression::IntegerLiteral(_) | Expression::BigIntegerLiteral(_) | Expression::RealLiteral(_) => true,
+            Expression::Combination {
+                left: None,
+                operator: Operator(Token::Minus, Location::Prefix),
+                right: Some(right),
+            } => Self::is_number_literal(right),
+            _ => false,
+        }
+    }
+
     fn parse_expression(&mut self, precedence: Precedence) -> Result<Expression, SyntaxError> {
         // println!("{:?}", self.iterator.peek());
         let expression = if let Some(tok) = self.iterator.peek() {
@@ -256,13 +496,18 @@ impl<I: Iterator<Item=Token>> Parser<I> {
                 // literals and prefix expressions
                 Token::Identifier(_)
                 | Token::Integer(_)
+                | Token::BigInteger(_)
                 | Token::Real(_)
                 | Token::True
                 | Token::False
                 | Token::Not
                 | Token::Minus
+                | Token::SetDifference
                 | Token::Undefined
                 | Token::LeftParen
+                | Token::LeftSquareBracket
+                | Token::LeftMultisetBrace
+                | Token::FuncFn
                 | Token::Bar => self.parse_prefix_expression().map(Some),
 
                 // piecewise block
@@ -281,18 +526,54 @@ impl<I: Iterator<Item=Token>> Parser<I> {
             Err(value) => Err(value)
         }?;
 
+        // whether the expression so far is a number times a name, which more names can follow
+        let mut names_follow = false;
         loop {
+            let after_name = std::mem::take(&mut names_follow);
             match self.iterator.peek() {
                 Some(&Token::Semicolon)
                 | Some(&Token::RightParen)
                 | Some(&Token::Comma)
+                | Some(&Token::DoubleDot)
                 | Some(&Token::If)
                 | Some(&Token::Else)
                 | Some(&Token::RightBrace)
+                | Some(&Token::RightSquareBracket)
+                | Some(&Token::RightMultisetBrace)
+                | Some(&Token::Where)
+                | Some(&Token::Colon)
+                | Some(&Token::Assign)
+                | Some(&Token::For)
                 | Some(&Token::Bar) => break Ok(()),
-                Some(&Token::Bang) => {
+                Some(&Token::Bang) | Some(&Token::Imaginary) => {
                     expression = self.parse_postfix_expression(expression)?;
                 },
+                // `x => x ^ 2` and `(a, b) -> a + b` are function literals, whose bodies extend as far as possible
+                Some(&Token::FatArrow) | Some(&Token::SkinnyArrow) => {
+                    if precedence > Precedence::Lowest {
+                        break Ok(())
+                    };
+                    self.iterator.next();
+                    expression = Expression::FunctionLiteral {
+                        parameters: Self::parameters_from(expression)?,
+                        body: Box::new(self.parse_expression(Precedence::Lowest)?),
+                    };
+                },
+                // a number followed by names multiplies them, as in `4x`, `3 kg`, `2 kg m` or `9.81 m/s^2`;
+                // this binds tighter than `/`, so `1/2x` is `1/(2x)` and `3 kg / 2 s` divides by two seconds
+                Some(&Token::Identifier(_)) if after_name || Self::is_number_literal(&expression) => {
+                    if precedence >= Precedence::Negative {
+                        break Ok(())
+                    };
+                    expression = Expression::Combination {
+                        left: Some(Box::new(expression)),
+                        operator: Operator(Token::Multiply, Location::Infix),
+                        right: Some(Box::new(self.parse_expression(Precedence::Negative)?)),
+                    };
+                    names_follow = true;
+                },
+                // the name is the next factor of a number times names
+                Some(&Token::Identifier(_)) if precedence >= Precedence::Negative => break Ok(()),
                 Some(next_token) => {
                     let peek_precedence = Operator(next_token.clone(), Location::Infix).precedence()?;
                     if precedence < peek_precedence {
@@ -330,32 +611,28 @@ impl<I: Iterator<Item=Token>> Parser<I> {
         // current token is fn
         self.iterator.next();
 
+        // without a name this is a function literal on its own
+        if self.iterator.peek() == Some(&LeftParen) {
+            let expression = self.parse_function_literal()?;
+            if self.iterator.next() != Some(Semicolon) {
+                return Err("Missing semicolon after expression statement.".into())
+            };
+            return Ok(ExpressionStatement { expression })
+        };
+
         // next token should be an identifier
         if let Some(Identifier(identifier)) = self.iterator.next() {
             // next token should be left paren
             if !self.iterator.next_if(|token| token == &LeftParen).is_some() {
                 return Err("Expected '(' after function identifier.".into());
             };
-            let mut parameters = vec![];
-            loop {
-                // next should be alternating identifiers and commas
-                // after the first parameter there should
-                match self.iterator.next() {
-                    Some(Identifier(name)) => {
-                        parameters.push(Parameter { name });
-                        match self.iterator.next() {
-                            // or break happily if the next token is a right paren
-                            Some(RightParen) => break Ok(()),
-                            // eat up to one comma
-                            Some(Comma) => continue,
-                            _ => break Err("Invalid function declaration syntax."),
-                        }
+            let parameters = self.parse_parameters()?;
 
-                    }
-                    Some(RightParen) => break Ok(()),
-                    _ => break Err("Invalid function declaration syntax."),
-                };
-            }?;
+            // an optional codomain, `-> R`
+            let codomain = match self.iterator.next_if(|token| token == &SkinnyArrow) {
+                Some(_) => Some(self.parse_expression(Precedence::Lowest)?),
+                None => None,
+            };
 
             if !self.iterator.next_if(|token| token == &Assign).is_some() {
                 return Err("Expected '=' after function parameters.".into());
@@ -364,11 +641,15 @@ impl<I: Iterator<Item=Token>> Parser<I> {
             let expression = self.parse_expression(Precedence::Lowest)?;
 
             // eat semicolon
-            self.iterator.next().map(drop);
+            if self.iterator.next_if(|token| token != &Semicolon).is_some() {
+                return Err("Missing semicolon after function declaration.".into())
+            };
+            self.iterator.next();
 
             Ok(FunctionDeclaration {
                 identifier,
                 parameters,
+                codomain,
                 expression
             })
         } else {
@@ -376,6 +657,64 @@ impl<I: Iterator<Item=Token>> Parser<I> {
         }
     }
 
+    // the parameters of a function after its left paren, up to and including the right paren
+    fn parse_parameters(&mut self) -> Result<Vec<Parameter>, SyntaxError> {
+        use Token::*;
+
+        let mut parameters = vec![];
+        loop {
+            // next should be alternating identifiers and commas
+            // after the first parameter there should
+            match self.iterator.next() {
+                Some(Identifier(name)) => {
+                    // an optional domain, `x: R` or `x in R`
+                    let domain = match self.iterator.next_if(|token| token == &Colon || token == &In) {
+                        Some(_) => Some(self.parse_expression(Precedence::Lowest)?),
+                        None => None,
+                    };
+                    parameters.push(Parameter { name, domain });
+                    match self.iterator.next() {
+                        // or break happily if the next token is a right paren
+                        Some(RightParen) => break Ok(parameters),
+                        // eat up to one comma
+                        Some(Comma) => continue,
+                        _ => break Err("Invalid function declaration syntax.".into()),
+                    }
+
+                }
+                Some(RightParen) => break Ok(parameters),
+                _ => break Err("Invalid function declaration syntax.".into()),
+            };
+        }
+    }
+
+    // `fn(x) = x ^ 2` is the same as `x => x ^ 2`, once the `fn` has been taken
+    fn parse_function_literal(&mut self) -> Result<Expression, SyntaxError> {
+        if self.iterator.next_if(|token| token == &Token::LeftParen).is_none() {
+            return Err("Expected '(' or a function identifier after fn.".into());
+        };
+        let parameters = self.parse_parameters()?;
+        if self.iterator.next_if(|token| token == &Token::Assign).is_none() {
+            return Err("Expected '=' after function parameters.".into());
+        };
+        Ok(Expression::FunctionLiteral {
+            parameters,
+            body: Box::new(self.parse_expression(Precedence::Lowest)?),
+        })
+    }
+
+    // `import <module>;`
+    fn parse_import_statement(&mut self) -> Result<Statement, SyntaxError> {
+        // current token is import
+        self.iterator.next();
+
+        match (self.iterator.next(), self.iterator.next()) {
+            (Some(Token::Identifier(module)), Some(Token::Semicolon)) => Ok(Statement::Import { module }),
+            (Some(Token::Identifier(_)), _) => Err("Missing semicolon after import statement.".into()),
+            _ => Err("Expected a module name after import.".into()),
+        }
+    }
+
     fn parse_assignment_statement(&mut self) -> Result<Statement, SyntaxError> {
         use Statement::*;
         use Token::*;
@@ -388,10 +727,13 @@ impl<I: Iterator<Item=Token>> Parser<I> {
             // next token should be =
             if self.iterator.next_if(|token| token == &Assign).is_some() {
                 // next set of tokens should form an expression
-                let expression = self.parse_expression(Precedence::Lowest)?;
+                let expression = Self::enumeration(self.parse_expression(Precedence::Lowest)?);
                 
                 // eat semicolon
-                self.iterator.next().map(drop);
+                if self.iterator.next_if(|token| token != &Semicolon).is_some() {
+                    return Err("Missing semicolon after assignment.".into())
+                };
+                self.iterator.next();
 
                 // return assignment statement
                 Ok(Assignment {
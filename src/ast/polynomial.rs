@@ -0,0 +1,324 @@
+use super::errors::RuntimeError;
+use super::object::{Object, MAX_EXACT_POWER_BITS};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, self};
+
+// rational roots are recovered exactly when their denominator is at most this
+const MAX_ROOT_DENOMINATOR: i64 = 100;
+
+// a polynomial in one variable with numeric coefficients
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Polynomial {
+    pub coefficients: Vec<Object>,  // lowest power first and never ending in zero, so the zero polynomial has none
+    pub variable: String,
+}
+
+fn is_zero(value: &Object) -> bool {
+    value.is_number() && value == &Object::Integer(0)
+}
+
+impl Polynomial {
+    pub fn new(mut coefficients: Vec<Object>, variable: &str) -> Self {
+        while coefficients.last().is_some_and(is_zero) {
+            coefficients.pop();
+        }
+        Polynomial { coefficients, variable: variable.to_string() }
+    }
+
+    // the polynomial `x` itself
+    pub fn identity(variable: &str) -> Self {
+        Polynomial::new(vec![Object::Integer(0), Object::Integer(1)], variable)
+    }
+
+    // numbers are constant polynomials in any variable
+    pub fn from_object(value: &Object, variable: &str) -> Option<Self> {
+        match value {
+            Object::Polynomial(polynomial) => Some(polynomial.clone()),
+            number if number.is_number() => Some(Polynomial::new(vec![number.clone()], variable)),
+            _ => None,
+        }
+    }
+
+    // both sides as polynomials when at least one is a polynomial and the other is a polynomial or a number
+    pub fn operands(left: &Object, right: &Object) -> Option<(Self, Self)> {
+        let variable = match (left, right) {
+            (Object::Polynomial(polynomial), _) | (_, Object::Polynomial(polynomial)) => polynomial.variable.clone(),
+            _ => return None,
+        };
+        Some((Polynomial::from_object(left, &variable)?, Polynomial::from_object(right, &variable)?))
+    }
+
+    // constants fit in with a polynomial in any variable
+    fn shared_variable(&self, other: &Self) -> Result<String, RuntimeError> {
+        if self.variable == other.variable || other.coefficients.len() <= 1 {
+            Ok(self.variable.clone())
+        } else if self.coefficients.len() <= 1 {
+            Ok(other.variable.clone())
+        } else {
+            Err(RuntimeError::OperatorError(format!("Cannot combine polynomials in {} and {}.", self.variable, other.variable)))
+        }
+    }
+
+    fn coefficient(&self, power: usize) -> Object {
+        self.coefficients.get(power).cloned().unwrap_or(Object::Integer(0))
+    }
+
+    fn is_exact(&self) -> bool {
+        self.coefficients.iter().all(Object::is_exact)
+    }
+
+    // the zero polynomial has no degree
+    pub fn degree(&self) -> Object {
+        match self.coefficients.len() {
+            0 => Object::Undefined,
+            length => Object::Integer(length as i64 - 1),
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Result<Self, RuntimeError> {
+        let variable = self.shared_variable(other)?;
+        let length = self.coefficients.len().max(other.coefficients.len());
+        let coefficients = (0..length).map(|power| self.coefficient(power) + other.coefficient(power)).collect::<Result<_, _>>()?;
+        Ok(Polynomial::new(coefficients, &variable))
+    }
+
+    pub fn scale(&self, factor: &Object) -> Result<Self, RuntimeError> {
+        let coefficients = self.coefficients.iter().map(|coefficient| factor.clone() * coefficient.clone()).collect::<Result<_, _>>()?;
+        Ok(Polynomial::new(coefficients, &self.variable))
+    }
+
+    pub fn sub(&self, other: &Self) -> Result<Self, RuntimeError> {
+        self.add(&other.scale(&Object::Integer(-1))?)
+    }
+
+    pub fn mul(&self, other: &Self) -> Result<Self, RuntimeError> {
+        let variable = self.shared_variable(other)?;
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            return Ok(Polynomial::new(vec![], &variable))
+        };
+        let mut coefficients = vec![Object::Integer(0); self.coefficients.len() + other.coefficients.len() - 1];
+        for (power, left) in self.coefficients.iter().enumerate() {
+            for (power2, right) in other.coefficients.iter().enumerate() {
+                coefficients[power + power2] = (coefficients[power + power2].clone() + (left.clone() * right.clone())?)?;
+            }
+        }
+        Ok(Polynomial::new(coefficients, &variable))
+    }
+
+    // by repeated squaring
+    pub fn pow(&self, mut exponent: u64) -> Result<Self, RuntimeError> {
+        // every coefficient of the result can grow by the size of the largest one each time it is multiplied
+        let bits = self.coefficients.iter().map(|coefficient| match coefficient.fraction() {
+            Some((numerator, denominator)) => numerator.bits() + denominator.bits(),
+            None => 64,
+        }).max().unwrap_or(0) + 64 - (self.coefficients.len() as u64).leading_zeros() as u64;
+        let terms = (self.coefficients.len() as u64).saturating_sub(1).saturating_mul(exponent).saturating_add(1);
+        if terms.saturating_mul(bits).saturating_mul(exponent) > MAX_EXACT_POWER_BITS {
+            return Err(RuntimeError::OperatorError(format!("Cannot raise {} to {} (the result would be too large).", self, exponent)))
+        };
+        let (mut base, mut result) = (self.clone(), Polynomial::new(vec![Object::Integer(1)], &self.variable));
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.mul(&base)?;
+            };
+            exponent /= 2;
+            if exponent > 0 {
+                base = base.mul(&base)?;
+            };
+        }
+        Ok(result)
+    }
+
+    // long division, giving the quotient and the remainder
+    pub fn divide(&self, divisor: &Self) -> Result<(Self, Self), RuntimeError> {
+        let variable = self.shared_variable(divisor)?;
+        let leading = match divisor.coefficients.last() {
+            Some(leading) => leading,
+            None => return Err(RuntimeError::OperatorError(format!("Cannot divide {} by the zero polynomial.", self))),
+        };
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![Object::Integer(0); self.coefficients.len().saturating_sub(divisor.coefficients.len()) + 1];
+        while remainder.len() >= divisor.coefficients.len() {
+            let shift = remainder.len() - divisor.coefficients.len();
+            let factor = (remainder.last().unwrap().clone() / leading.clone())?;
+            for (power, coefficient) in divisor.coefficients.iter().enumerate() {
+                remainder[shift + power] = (remainder[shift + power].clone() - (factor.clone() * coefficient.clone())?)?;
+            }
+            // the leading term cancels, even when rounding leaves a trace of it behind
+            remainder.pop();
+            while remainder.last().is_some_and(is_zero) {
+                remainder.pop();
+            }
+            quotient[shift] = factor;
+        }
+        Ok((Polynomial::new(quotient, &variable), Polynomial::new(remainder, &variable)))
+    }
+
+    // by horner's method, so the value can be anything that numbers can be added to and multiplied by
+    pub fn evaluate(&self, value: &Object) -> Result<Object, RuntimeError> {
+        self.coefficients.iter().rev().try_fold(Object::Integer(0), |acc, coefficient| (acc * value.clone())? + coefficient.clone())
+    }
+
+    pub fn derivative(&self) -> Result<Self, RuntimeError> {
+        let coefficients = self.coefficients.iter().enumerate().skip(1)
+            .map(|(power, coefficient)| Object::Integer(power as i64) * coefficient.clone())
+            .collect::<Result<_, _>>()?;
+        Ok(Polynomial::new(coefficients, &self.variable))
+    }
+
+    // scaled so that the leading coefficient is one
+    fn monic(&self) -> Result<Self, RuntimeError> {
+        match self.coefficients.last() {
+            Some(leading) => self.scale(&(Object::Integer(1) / leading.clone())?),
+            None => Ok(self.clone()),
+        }
+    }
+
+    // the monic greatest common divisor, by euclid's algorithm
+    pub fn gcd(&self, other: &Self) -> Result<Self, RuntimeError> {
+        let variable = self.shared_variable(other)?;
+        let (mut left, mut right) = (self.clone(), other.clone());
+        while !right.coefficients.is_empty() {
+            let (_, remainder) = left.divide(&right)?;
+            left = right;
+            right = remainder;
+        }
+        left.variable = variable;
+        left.monic()
+    }
+
+    // the set of real roots; rational roots of polynomials with exact coefficients are found exactly
+    pub fn roots(&self) -> Result<Object, RuntimeError> {
+        if self.coefficients.is_empty() {
+            return Err(RuntimeError::OperatorError("Cannot list the roots of the zero polynomial.".into()))
+        };
+        if self.coefficients.iter().any(|coefficient| coefficient.as_real().is_none()) {
+            return Err(RuntimeError::TypeError(format!("Cannot find the roots of {} (expected real coefficients).", self)))
+        };
+        // every root of the square-free part is a simple root, which the numerical method finds accurately
+        let polynomial = if self.is_exact() {
+            self.divide(&self.gcd(&self.derivative()?)?)?.0
+        } else {
+            self.clone()
+        };
+        let mut roots = BTreeSet::new();
+        if polynomial.coefficients.len() == 2 && polynomial.is_exact() {
+            roots.insert((-(polynomial.coefficients[0].clone() / polynomial.coefficients[1].clone())?)?);
+        } else {
+            let coefficients = polynomial.coefficients.iter().map(|coefficient| coefficient.as_real().unwrap()).collect::<Vec<_>>();
+            for root in real_roots(&coefficients) {
+                roots.insert(polynomial.exact_root(root)?);
+            }
+        }
+        Ok(Object::set(roots))
+    }
+
+    // a rational root close to an approximate one, if there is one with a small denominator
+    fn exact_root(&self, approximation: f64) -> Result<Object, RuntimeError> {
+        if self.is_exact() && approximation.abs() < 1e15 {
+            for denominator in 1..=MAX_ROOT_DENOMINATOR {
+                let numerator = (approximation * denominator as f64).round() as i64;
+                let candidate = Object::rational(numerator.into(), denominator.into());
+                if is_zero(&self.evaluate(&candidate)?) {
+                    return Ok(candidate)
+                };
+            }
+        };
+        Ok(Object::Real(approximation))
+    }
+}
+
+fn evaluate_real(coefficients: &[f64], value: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, coefficient| acc * value + coefficient)
+}
+
+// every complex root by the durand-kerner method, keeping the real ones
+fn real_roots(coefficients: &[f64]) -> Vec<f64> {
+    let degree = coefficients.len() - 1;
+    let monic = coefficients.iter().map(|coefficient| coefficient / coefficients[degree]).collect::<Vec<_>>();
+    let evaluate = |(re, im): (f64, f64)| monic.iter().rev().fold((0.0, 0.0), |(a, b), coefficient| (a * re - b * im + coefficient, a * im + b * re));
+    // start from points spread around a circle that contains every root
+    let radius = 1.0 + monic[..degree].iter().fold(0.0, |max: f64, coefficient| max.max(coefficient.abs()));
+    let mut roots = (0..degree).map(|k| {
+        let angle = 2.0 * std::f64::consts::PI * k as f64 / degree as f64 + 0.4;
+        (radius * angle.cos(), radius * angle.sin())
+    }).collect::<Vec<_>>();
+    for _ in 0..1000 {
+        let mut change = 0.0;
+        for i in 0..degree {
+            let (mut re, mut im) = (1.0, 0.0);
+            for j in (0..degree).filter(|j| *j != i) {
+                let (a, b) = (roots[i].0 - roots[j].0, roots[i].1 - roots[j].1);
+                let product = (re * a - im * b, re * b + im * a);
+                re = product.0;
+                im = product.1;
+            }
+            let (value_re, value_im) = evaluate(roots[i]);
+            let denominator = re * re + im * im;
+            let step = ((value_re * re + value_im * im) / denominator, (value_im * re - value_re * im) / denominator);
+            if step.0.is_finite() && step.1.is_finite() {
+                roots[i] = (roots[i].0 - step.0, roots[i].1 - step.1);
+                change += step.0.abs() + step.1.abs();
+            };
+        }
+        if change < 1e-15 {
+            break
+        };
+    }
+    let derivative = coefficients.iter().enumerate().skip(1).map(|(power, coefficient)| power as f64 * coefficient).collect::<Vec<_>>();
+    let mut real = roots.into_iter()
+        .filter(|(re, im)| im.abs() <= 1e-6 * re.abs().max(1.0))
+        .map(|(mut root, _)| {
+            // polish with a few steps of newton's method
+            for _ in 0..5 {
+                let slope = evaluate_real(&derivative, root);
+                if slope != 0.0 {
+                    root -= evaluate_real(coefficients, root) / slope;
+                };
+            }
+            root
+        })
+        .collect::<Vec<_>>();
+    real.sort_by(|left, right| left.total_cmp(right));
+    // a repeated root may have been found more than once
+    real.dedup_by(|later, earlier| (*later - *earlier).abs() <= 1e-6 * earlier.abs().max(1.0));
+    real
+}
+
+impl Display for Polynomial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.coefficients.is_empty() {
+            return write!(f, "0")
+        };
+        let mut first = true;
+        for (power, coefficient) in self.coefficients.iter().enumerate().rev() {
+            if is_zero(coefficient) {
+                continue
+            };
+            let negative = coefficient.as_real().is_some_and(|value| value < 0.0);
+            let magnitude = if negative { (-coefficient.clone()).map_err(|_| fmt::Error)? } else { coefficient.clone() };
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+                (true, false) => (),
+            };
+            first = false;
+            let number = magnitude.to_string();
+            if power > 0 && magnitude != Object::Integer(1) {
+                // fractions and complex numbers are bracketed so that they clearly multiply the variable
+                if number.contains(&['/', ' '][..]) {
+                    write!(f, "({})", number)?;
+                } else {
+                    write!(f, "{}", number)?;
+                }
+            };
+            match power {
+                0 => write!(f, "{}", number)?,
+                1 => write!(f, "{}", self.variable)?,
+                _ => write!(f, "{}^{}", self.variable, power)?,
+            };
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use super::errors::RuntimeError;
+use super::object::Object;
+use std::collections::HashMap;
+use num_bigint::BigInt;
+use num_integer::Integer as _;
+use num_traits::{Signed, ToPrimitive};
+
+// discrete logarithms search up to about the square root of the modulus,
+// or through every power when the base has no inverse
+const MAX_DISCRETE_LOG_MODULUS: i64 = 1 << 40;
+const MAX_DISCRETE_LOG_SEARCH: i64 = 1 << 24;
+
+fn residue(value: i128, modulus: i64) -> Object {
+    Object::Residue { value: value.rem_euclid(modulus as i128) as i64, modulus }
+}
+
+fn big_residue(value: &BigInt, modulus: i64) -> i64 {
+    value.mod_floor(&BigInt::from(modulus)).to_i64().unwrap()
+}
+
+// `a mod n`
+pub fn reduce(value: &Object, modulus: &Object) -> Result<Object, RuntimeError> {
+    let modulus = match modulus {
+        Object::Integer(modulus) if *modulus > 0 => *modulus,
+        Object::BigInteger(modulus) if modulus.is_positive() => {
+            return Err(RuntimeError::OperatorError(format!("Cannot reduce mod {} (moduli must fit in 64 bits).", modulus)))
+        },
+        obj => return Err(RuntimeError::TypeError(format!("Expected a positive integer modulus got {}.", obj))),
+    };
+    match value {
+        Object::Integer(value) => Ok(residue(*value as i128, modulus)),
+        Object::BigInteger(value) => Ok(residue(big_residue(value, modulus) as i128, modulus)),
+        // a fraction is its numerator times the inverse of its denominator, when there is one
+        Object::Rational { numerator, denominator } => Ok(match inverse(*denominator, modulus) {
+            Some(inverse) => residue(*numerator as i128 * inverse as i128, modulus),
+            None => Object::Undefined,
+        }),
+        Object::BigRational { numerator, denominator } => Ok(match inverse(big_residue(denominator, modulus), modulus) {
+            Some(inverse) => residue(big_residue(numerator, modulus) as i128 * inverse as i128, modulus),
+            None => Object::Undefined,
+        }),
+        Object::Residue { value, modulus: modulus2 } if modulus2 % modulus == 0 => Ok(residue(*value as i128, modulus)),
+        obj => Err(RuntimeError::TypeError(format!("Cannot reduce {} mod {} (expected an integer).", obj, modulus))),
+    }
+}
+
+// residues mod the same number, where integers are reduced to match the other side
+pub fn operands(left: &Object, right: &Object) -> Option<Result<(i64, i64, i64), RuntimeError>> {
+    let integer = |value: &Object, modulus: i64| match value {
+        Object::Integer(value) => Some((*value as i128).rem_euclid(modulus as i128) as i64),
+        Object::BigInteger(value) => Some(big_residue(value, modulus)),
+        _ => None,
+    };
+    match (left, right) {
+        (Object::Residue { value, modulus }, Object::Residue { value: value2, modulus: modulus2 }) => Some(if modulus == modulus2 {
+            Ok((*value, *value2, *modulus))
+        } else {
+            Err(RuntimeError::OperatorError(format!("Cannot combine {} and {} (their moduli differ).", left, right)))
+        }),
+        (Object::Residue { value, modulus }, other) => Some(Ok((*value, integer(other, *modulus)?, *modulus))),
+        (other, Object::Residue { value, modulus }) => Some(Ok((integer(other, *modulus)?, *value, *modulus))),
+        _ => None,
+    }
+}
+
+pub fn add(left: i64, right: i64, modulus: i64) -> Object {
+    residue(left as i128 + right as i128, modulus)
+}
+
+pub fn sub(left: i64, right: i64, modulus: i64) -> Object {
+    residue(left as i128 - right as i128, modulus)
+}
+
+pub fn mul(left: i64, right: i64, modulus: i64) -> Object {
+    residue(left as i128 * right as i128, modulus)
+}
+
+// multiplying by the inverse, so undefined when the divisor shares a factor with the modulus
+pub fn div(left: i64, right: i64, modulus: i64) -> Object {
+    match inverse(right, modulus) {
+        Some(inverse) => mul(left, inverse, modulus),
+        None => Object::Undefined,
+    }
+}
+
+// the x with value * x ≡ 1, from the extended Euclidean algorithm
+pub fn inverse(value: i64, modulus: i64) -> Option<i64> {
+    let (mut old_r, mut r) = ((value as i128).rem_euclid(modulus as i128), modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    match old_r {
+        1 => Some(old_s.rem_euclid(modulus as i128) as i64),
+        // everything is congruent mod 1
+        _ if modulus == 1 => Some(0),
+        _ => None,
+    }
+}
+
+// negative powers are powers of the inverse
+pub fn pow(base: i64, exponent: &BigInt, modulus: i64) -> Object {
+    let base = if exponent.is_negative() {
+        match inverse(base, modulus) {
+            Some(inverse) => inverse,
+            None => return Object::Undefined,
+        }
+    } else {
+        base
+    };
+    residue(big_residue(&BigInt::from(base).modpow(&exponent.abs(), &BigInt::from(modulus)), modulus) as i128, modulus)
+}
+
+// `a ≡ b (mod n)`, which parses as a ≡ (b mod n)
+pub fn congruent(left: &Object, right: &Object) -> Result<Object, RuntimeError> {
+    match operands(left, right) {
+        Some(operands) => {
+            let (left, right, _) = operands?;
+            Ok(Object::Boolean(left == right))
+        },
+        None => Err(RuntimeError::OperatorError(format!("Cannot check whether {} ≡ {} without a modulus (e.g. `a ≡ b (mod n)`).", left, right))),
+    }
+}
+
+// the chinese remainder theorem: the single residue satisfying each congruence, or None when they conflict
+pub fn crt(congruences: &[(i64, i64)]) -> Result<Option<(i64, i64)>, RuntimeError> {
+    let mut combined = (0i128, 1i128);
+    for (value, modulus) in congruences {
+        let (value, modulus) = (*value as i128, *modulus as i128);
+        let (current, current_modulus) = combined;
+        let divisor = current_modulus.gcd(&modulus);
+        if (value - current) % divisor != 0 {
+            return Ok(None)
+        };
+        // current + current_modulus * t ≡ value (mod modulus)
+        let step = modulus / divisor;
+        let t = ((value - current) / divisor).rem_euclid(step)
+            * inverse((current_modulus / divisor % step) as i64, step as i64).unwrap() as i128 % step;
+        let lcm = current_modulus * step;
+        if lcm > i64::MAX as i128 {
+            return Err(RuntimeError::OperatorError("Cannot combine the congruences (the combined modulus is too large).".to_string()))
+        };
+        combined = ((current + current_modulus * t).rem_euclid(lcm), lcm);
+    }
+    Ok(Some((combined.0 as i64, combined.1 as i64)))
+}
+
+// the smallest k ≥ 0 with base^k ≡ target, or None when there is no such k
+pub fn discrete_log(base: i64, target: i64, modulus: i64) -> Result<Option<i64>, RuntimeError> {
+    if modulus > MAX_DISCRETE_LOG_MODULUS {
+        return Err(RuntimeError::OperatorError(format!("Cannot take discrete logarithms mod {} (too large).", modulus)))
+    };
+    let power = |exponent: i64| match pow(base, &BigInt::from(exponent), modulus) {
+        Object::Residue { value, .. } => value,
+        _ => unreachable!(),
+    };
+    let step = (modulus as f64).sqrt().ceil() as i64;
+    match inverse(power(step), modulus) {
+        // baby-step giant-step: base^(i * step + j) ≡ target exactly when base^j ≡ target * base^(-i * step)
+        Some(giant) => {
+            let mut baby_steps = HashMap::new();
+            for j in (0..step).rev() {
+                baby_steps.insert(power(j), j);
+            }
+            let mut current = target;
+            for i in 0..=step {
+                if let Some(j) = baby_steps.get(&current) {
+                    return Ok(Some(i * step + j))
+                };
+                current = (current as i128 * giant as i128 % modulus as i128) as i64;
+            }
+            Ok(None)
+        },
+        // the powers repeat within `modulus` steps, so they can be searched directly
+        None if modulus <= MAX_DISCRETE_LOG_SEARCH => {
+            let mut current = 1 % modulus;
+            for k in 0..=modulus {
+                if current == target {
+                    return Ok(Some(k))
+                };
+                current = (current as i128 * base as i128 % modulus as i128) as i64;
+            }
+            Ok(None)
+        },
+        None => Err(RuntimeError::OperatorError(format!("Cannot take discrete logarithms of powers of {} mod {} (too large without an inverse).", base, modulus))),
+    }
+}
@@ -11,9 +11,13 @@ pub enum Statement {
     FunctionDeclaration {
         identifier: String,
         parameters: Vec<Parameter>,
+        codomain: Option<Expression>,  // the set results must belong to, from `-> R`
         expression: Expression
     },
     ExpressionStatement {
         expression: Expression,
     },
+    Import {
+        module: String,
+    },
 }
\ No newline at end of file
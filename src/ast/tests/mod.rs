@@ -1,6 +1,7 @@
 pub mod test_objects;
 use crate::ast::expression::Expression;
 use crate::ast::parameter::Parameter;
+use crate::ast::generator::Generator;
 use crate::ast::parser::Parser;
 use crate::ast::statement::Statement::*;
 use crate::ast::location::Location::*;
@@ -9,9 +10,9 @@ use crate::interpreter::token::Token;
 use crate::ast::operator::Operator;
 
 macro_rules! parse {
-    ($text:expr, $statements:expr) => {
+    ($text:expr, $statements:expr) => {{
         assert_eq!(Parser::new(LexerIterator::new($text.chars().peekable())).parse_program().unwrap(), $statements);
-    };
+    }};
 }
 
 macro_rules! bad_parsing {
@@ -32,7 +33,7 @@ fn test_real_assignment() {
         "let pi = 3.14;",
         vec![Assignment {
             identifier: "pi".into(),
-            expression: Expression::RealLiteral(3.14)
+            expression: Expression::RealLiteral("3.14".into())
         }]
     );
 }
@@ -77,8 +78,10 @@ fn test_function_statement() {
         vec![FunctionDeclaration {
             identifier: "area".into(),
             parameters: vec![Parameter {
-                name: "radius".into()
+                name: "radius".into(),
+                domain: None
             }],
+            codomain: None,
             expression: Expression::Combination {
                 left: Some(Box::new(Expression::Identifier("pi".into()))),
                 operator: Operator(Token::Multiply, Infix),
@@ -100,12 +103,15 @@ fn test_function_statement_with_params() {
             identifier: "area".into(),
             parameters: vec![
                 Parameter {
-                    name: "width".into()
+                    name: "width".into(),
+                    domain: None
                 },
                 Parameter {
-                    name: "height".into()
+                    name: "height".into(),
+                    domain: None
                 }
             ],
+            codomain: None,
             expression: Expression::Combination {
                 left: Some(Box::new(Expression::Identifier("width".into()))),
                 operator: Operator(Token::Multiply, Infix),
@@ -125,13 +131,15 @@ fn test_multiple_statements() {
         vec![
             Assignment {
                 identifier: "pi".into(),
-                expression: Expression::RealLiteral(3.14)
+                expression: Expression::RealLiteral("3.14".into())
             },
             FunctionDeclaration {
                 identifier: "area".into(),
                 parameters: vec![Parameter {
-                    name: "radius".into()
+                    name: "radius".into(),
+                    domain: None
                 }],
+                codomain: None,
                 expression: Expression::Combination {
                     left: Some(Box::new(Expression::Identifier("pi".into()))),
                     operator: Operator(Token::Multiply, Infix),
@@ -534,7 +542,7 @@ fn test_piecewise_block_expression() {
                         operator: Operator(Token::LessThan, Infix),
                         right: Some(Box::new(Expression::IntegerLiteral(0)))
                     }),
-                    (Expression::RealLiteral(0.5), Expression::Combination {
+                    (Expression::RealLiteral("0.5".into()), Expression::Combination {
                         left: Some(Box::new(Expression::Identifier("x".into()))),
                         operator: Operator(Token::Equals, Infix),
                         right: Some(Box::new(Expression::IntegerLiteral(0)))
@@ -571,7 +579,7 @@ fn test_piecewise_block_in_assignment_statement() {
                             operator: Operator(Token::LessThan, Infix),
                             right: Some(Box::new(Expression::IntegerLiteral(0)))
                         }),
-                        (Expression::RealLiteral(0.5), Expression::Combination {
+                        (Expression::RealLiteral("0.5".into()), Expression::Combination {
                             left: Some(Box::new(Expression::Identifier("x".into()))),
                             operator: Operator(Token::Equals, Infix),
                             right: Some(Box::new(Expression::IntegerLiteral(0)))
@@ -699,9 +707,9 @@ fn test_set_literal_with_reals() {
         vec![
             ExpressionStatement {
                 expression: Expression::SetLiteral(vec![
-                    Expression::RealLiteral(1.0),
-                    Expression::RealLiteral(2.0),
-                    Expression::RealLiteral(3.0),
+                    Expression::RealLiteral("1.0".into()),
+                    Expression::RealLiteral("2.0".into()),
+                    Expression::RealLiteral("3.0".into()),
                 ])
             }
         ]
@@ -716,13 +724,13 @@ fn test_set_union() {
             ExpressionStatement {
                 expression: Expression::Combination {
                     left: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(1.0),
-                        Expression::RealLiteral(2.0),
+                        Expression::RealLiteral("1.0".into()),
+                        Expression::RealLiteral("2.0".into()),
                     ]))),
                     operator: Operator(Token::Union, Infix),
                     right: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(3.0),
-                        Expression::RealLiteral(4.0),
+                        Expression::RealLiteral("3.0".into()),
+                        Expression::RealLiteral("4.0".into()),
                     ]))),
                 }
             }
@@ -738,13 +746,13 @@ fn test_set_difference() {
             ExpressionStatement {
                 expression: Expression::Combination {
                     left: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(1.0),
-                        Expression::RealLiteral(2.0),
+                        Expression::RealLiteral("1.0".into()),
+                        Expression::RealLiteral("2.0".into()),
                     ]))),
                     operator: Operator(Token::SetDifference, Infix),
                     right: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(2.0),
-                        Expression::RealLiteral(3.0),
+                        Expression::RealLiteral("2.0".into()),
+                        Expression::RealLiteral("3.0".into()),
                     ]))),
                 }
             }
@@ -760,13 +768,13 @@ fn test_set_symmetric_difference() {
             ExpressionStatement {
                 expression: Expression::Combination {
                     left: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(1.0),
-                        Expression::RealLiteral(2.0),
+                        Expression::RealLiteral("1.0".into()),
+                        Expression::RealLiteral("2.0".into()),
                     ]))),
                     operator: Operator(Token::SymmetricDifference, Infix),
                     right: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(2.0),
-                        Expression::RealLiteral(3.0),
+                        Expression::RealLiteral("2.0".into()),
+                        Expression::RealLiteral("3.0".into()),
                     ]))),
                 }
             }
@@ -782,13 +790,13 @@ fn test_set_intersection() {
             ExpressionStatement {
                 expression: Expression::Combination {
                     left: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(1.0),
-                        Expression::RealLiteral(2.0),
+                        Expression::RealLiteral("1.0".into()),
+                        Expression::RealLiteral("2.0".into()),
                     ]))),
                     operator: Operator(Token::Intersection, Infix),
                     right: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(3.0),
-                        Expression::RealLiteral(4.0),
+                        Expression::RealLiteral("3.0".into()),
+                        Expression::RealLiteral("4.0".into()),
                     ]))),
                 }
             }
@@ -804,13 +812,13 @@ fn test_set_subset() {
             ExpressionStatement {
                 expression: Expression::Combination {
                     left: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(1.0),
-                        Expression::RealLiteral(2.0),
+                        Expression::RealLiteral("1.0".into()),
+                        Expression::RealLiteral("2.0".into()),
                     ]))),
                     operator: Operator(Token::LessThan, Infix),
                     right: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(3.0),
-                        Expression::RealLiteral(4.0),
+                        Expression::RealLiteral("3.0".into()),
+                        Expression::RealLiteral("4.0".into()),
                     ]))),
                 }
             }
@@ -826,13 +834,13 @@ fn test_set_proper_subset() {
             ExpressionStatement {
                 expression: Expression::Combination {
                     left: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(1.0),
-                        Expression::RealLiteral(2.0),
+                        Expression::RealLiteral("1.0".into()),
+                        Expression::RealLiteral("2.0".into()),
                     ]))),
                     operator: Operator(Token::LessThanEquals, Infix),
                     right: Some(Box::new(Expression::SetLiteral(vec![
-                        Expression::RealLiteral(3.0),
-                        Expression::RealLiteral(4.0),
+                        Expression::RealLiteral("3.0".into()),
+                        Expression::RealLiteral("4.0".into()),
                     ]))),
                 }
             }
@@ -880,6 +888,258 @@ fn test_factorial_grouped() {
     )
 }
 
+#[test]
+fn test_imaginary_literal() {
+    parse!(
+        "1 + 2i;",
+        vec![
+            ExpressionStatement {
+                expression: Expression::Combination {
+                    left: Some(Box::new(Expression::IntegerLiteral(1))),
+                    operator: Operator(Token::Plus, Infix),
+                    right: Some(Box::new(Expression::Combination {
+                        left: Some(Box::new(Expression::IntegerLiteral(2))),
+                        operator: Operator(Token::Imaginary, Postfix),
+                        right: None,
+                    }))
+                }
+            }
+        ]
+    )
+}
+
+#[test]
+fn test_tuple_literal_expression() {
+    parse!(
+        "(1, 2 + 2, x);",
+        vec![ExpressionStatement {
+            expression: Expression::TupleLiteral(vec![
+                Expression::IntegerLiteral(1),
+                Expression::Combination {
+                    left: Some(Box::new(Expression::IntegerLiteral(2))),
+                    operator: Operator(Token::Plus, Infix),
+                    right: Some(Box::new(Expression::IntegerLiteral(2)))
+                },
+                Expression::Identifier("x".into()),
+            ])
+        }]
+    );
+}
+
+#[test]
+fn test_single_element_tuple_needs_trailing_comma() {
+    parse!(
+        "(1,); (1);",
+        vec![
+            ExpressionStatement {
+                expression: Expression::TupleLiteral(vec![Expression::IntegerLiteral(1)])
+            },
+            ExpressionStatement {
+                expression: Expression::IntegerLiteral(1)
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_index_expression() {
+    parse!(
+        "p[0] + 1;",
+        vec![ExpressionStatement {
+            expression: Expression::Combination {
+                left: Some(Box::new(Expression::Index {
+                    collection: Box::new(Expression::Identifier("p".into())),
+                    index: Box::new(Expression::IntegerLiteral(0))
+                })),
+                operator: Operator(Token::Plus, Infix),
+                right: Some(Box::new(Expression::IntegerLiteral(1)))
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_vector_literal_expression() {
+    parse!(
+        "[1, 2, 3,];",
+        vec![ExpressionStatement {
+            expression: Expression::VectorLiteral(vec![
+                Expression::IntegerLiteral(1),
+                Expression::IntegerLiteral(2),
+                Expression::IntegerLiteral(3),
+            ])
+        }]
+    );
+}
+
+#[test]
+fn test_matrix_literal_expression() {
+    parse!(
+        "[[1, 2], [3, 4]];",
+        vec![ExpressionStatement {
+            expression: Expression::MatrixLiteral(vec![
+                vec![Expression::IntegerLiteral(1), Expression::IntegerLiteral(2)],
+                vec![Expression::IntegerLiteral(3), Expression::IntegerLiteral(4)],
+            ])
+        }]
+    );
+}
+
+#[test]
+fn test_matrix_builder_expression() {
+    parse!(
+        "[ i * j where i in a, j in b ];",
+        vec![ExpressionStatement {
+            expression: Expression::MatrixBuilder {
+                expression: Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("i".into()))),
+                    operator: Operator(Token::Multiply, Infix),
+                    right: Some(Box::new(Expression::Identifier("j".into())))
+                }),
+                generators: vec![
+                    Generator { variable: "i".into(), set: Expression::Identifier("a".into()) },
+                    Generator { variable: "j".into(), set: Expression::Identifier("b".into()) },
+                ]
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_set_builder_expression() {
+    parse!(
+        "{ i in s: i > 0, i < 10 };",
+        vec![ExpressionStatement {
+            expression: Expression::SetBuilder {
+                variable: "i".into(),
+                parent_set: Box::new(Expression::Identifier("s".into())),
+                conditions: vec![
+                    Expression::Combination {
+                        left: Some(Box::new(Expression::Identifier("i".into()))),
+                        operator: Operator(Token::GreaterThan, Infix),
+                        right: Some(Box::new(Expression::IntegerLiteral(0)))
+                    },
+                    Expression::Combination {
+                        left: Some(Box::new(Expression::Identifier("i".into()))),
+                        operator: Operator(Token::LessThan, Infix),
+                        right: Some(Box::new(Expression::IntegerLiteral(10)))
+                    },
+                ]
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_set_comprehension_expression() {
+    parse!(
+        "{ x + y for x in a, y in b };",
+        vec![ExpressionStatement {
+            expression: Expression::SetComprehension {
+                expression: Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("x".into()))),
+                    operator: Operator(Token::Plus, Infix),
+                    right: Some(Box::new(Expression::Identifier("y".into())))
+                }),
+                generators: vec![
+                    Generator { variable: "x".into(), set: Expression::Identifier("a".into()) },
+                    Generator { variable: "y".into(), set: Expression::Identifier("b".into()) },
+                ]
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_complement_expression() {
+    parse!(
+        "\\ a \\/ b;",
+        vec![ExpressionStatement {
+            expression: Expression::Combination {
+                left: None,
+                operator: Operator(Token::SetDifference, Prefix),
+                right: Some(Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("a".into()))),
+                    operator: Operator(Token::Union, Infix),
+                    right: Some(Box::new(Expression::Identifier("b".into())))
+                }))
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_range_expression() {
+    parse!(
+        "{ 1, 3, ..., n };",
+        vec![ExpressionStatement {
+            expression: Expression::RangeLiteral {
+                first: Box::new(Expression::IntegerLiteral(1)),
+                second: Some(Box::new(Expression::IntegerLiteral(3))),
+                last: Box::new(Expression::Identifier("n".into())),
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_multiset_literal_expression() {
+    parse!(
+        "{: 1, 1, 2 :};",
+        vec![
+            ExpressionStatement {
+                expression: Expression::MultisetLiteral(vec![
+                    Expression::IntegerLiteral(1),
+                    Expression::IntegerLiteral(1),
+                    Expression::IntegerLiteral(2),
+                ])
+            }
+        ]
+    )
+}
+
+#[test]
+fn test_enumeration_expression() {
+    parse!(
+        "let Suits = { hearts, spades };",
+        vec![Assignment {
+            identifier: "Suits".into(),
+            expression: Expression::Enumeration(vec!["hearts".into(), "spades".into()])
+        }]
+    );
+    parse!(
+        "{ hearts, spades };",
+        vec![ExpressionStatement {
+            expression: Expression::SetLiteral(vec![Expression::Identifier("hearts".into()), Expression::Identifier("spades".into())])
+        }]
+    );
+}
+
+#[test]
+fn test_interval_expressions() {
+    parse!(
+        "[0, 1); (a, b];",
+        vec![
+            ExpressionStatement {
+                expression: Expression::IntervalLiteral {
+                    start: Box::new(Expression::IntegerLiteral(0)),
+                    end: Box::new(Expression::IntegerLiteral(1)),
+                    start_closed: true,
+                    end_closed: false,
+                }
+            },
+            ExpressionStatement {
+                expression: Expression::IntervalLiteral {
+                    start: Box::new(Expression::Identifier("a".into())),
+                    end: Box::new(Expression::Identifier("b".into())),
+                    start_closed: false,
+                    end_closed: true,
+                }
+            },
+        ]
+    );
+}
+
 bad_parsing!(test_equality_in_assignment, "let value == 123;");
 
 bad_parsing!(test_missing_semicolon_in_assignment, "let value = 123");
@@ -892,7 +1152,7 @@ bad_parsing!(test_bad_function_declaration, "fn foobar(a, b=2) = a * b;");
 
 bad_parsing!(
     test_function_declaration_without_identifier,
-    "fn (a, b) = a * b;"
+    "fn = a * b;"
 );
 
 bad_parsing!(
@@ -924,4 +1184,182 @@ bad_parsing!(test_if_outside_piecewise_block, "let x = 2 if true else 3;");
 
 bad_parsing!(test_else_outside_piecewise_block, "undefined else 2;");
 
-bad_parsing!(test_abs_val_never_closed, "|2 - 7;");
\ No newline at end of file
+bad_parsing!(test_tuple_without_commas, "(1 2);");
+
+bad_parsing!(test_index_never_closed, "p[0;");
+
+bad_parsing!(test_ragged_matrix_literal, "[[1, 2], [3]];");
+
+bad_parsing!(test_matrix_builder_without_generators, "[ i where ];");
+
+bad_parsing!(test_matrix_builder_with_bad_generator, "[ i where 1 in a ];");
+
+bad_parsing!(test_set_builder_without_variable, "{ 1 in s: true };");
+
+bad_parsing!(test_set_builder_without_conditions, "{ i in s: };");
+
+bad_parsing!(test_set_comprehension_without_generators, "{ x for };");
+
+bad_parsing!(test_range_too_many_members, "{ 1, 2, 3, ..., 10 };");
+bad_parsing!(test_range_without_end, "{ 1, ... };");
+bad_parsing!(test_multiset_never_closed, "{: 1, 2 };");
+bad_parsing!(test_interval_with_three_members, "[1, 2, 3);");
+bad_parsing!(test_abs_val_never_closed, "|2 - 7;");
+#[test]
+fn test_import_statement() {
+    parse!("import units;", vec![Import { module: "units".into() }]);
+}
+
+#[test]
+fn test_number_followed_by_name() {
+    parse!(
+        "-9.81 m/s^2;",
+        vec![ExpressionStatement {
+            expression: Expression::Combination {
+                left: Some(Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Combination {
+                        left: None,
+                        operator: Operator(Token::Minus, Prefix),
+                        right: Some(Box::new(Expression::RealLiteral("9.81".into()))),
+                    })),
+                    operator: Operator(Token::Multiply, Infix),
+                    right: Some(Box::new(Expression::Identifier("m".into()))),
+                })),
+                operator: Operator(Token::Division, Infix),
+                right: Some(Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("s".into()))),
+                    operator: Operator(Token::Exponent, Infix),
+                    right: Some(Box::new(Expression::IntegerLiteral(2))),
+                })),
+            }
+        }]
+    );
+}
+
+bad_parsing!(test_import_without_module, "import;");
+bad_parsing!(test_name_followed_by_name, "x y;");
+
+#[test]
+fn test_congruence_with_mod_clause() {
+    parse!(
+        "a ≡ b^2 (mod n);",
+        vec![ExpressionStatement {
+            expression: Expression::Combination {
+                left: Some(Box::new(Expression::Identifier("a".into()))),
+                operator: Operator(Token::Congruent, Infix),
+                right: Some(Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Combination {
+                        left: Some(Box::new(Expression::Identifier("b".into()))),
+                        operator: Operator(Token::Exponent, Infix),
+                        right: Some(Box::new(Expression::IntegerLiteral(2))),
+                    })),
+                    operator: Operator(Token::Mod, Infix),
+                    right: Some(Box::new(Expression::Identifier("n".into()))),
+                })),
+            }
+        }]
+    );
+}
+
+bad_parsing!(test_mod_clause_never_closed, "a ≡ b (mod n;");
+
+#[test]
+fn test_function_literal() {
+    parse!(
+        "x => x ^ 2;",
+        vec![ExpressionStatement {
+            expression: Expression::FunctionLiteral {
+                parameters: vec![Parameter { name: "x".into(), domain: None }],
+                body: Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("x".into()))),
+                    operator: Operator(Token::Exponent, Infix),
+                    right: Some(Box::new(Expression::IntegerLiteral(2))),
+                }),
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_function_literal_with_parameter_tuple() {
+    parse!(
+        "let add = (a, b) -> a + b;",
+        vec![Assignment {
+            identifier: "add".into(),
+            expression: Expression::FunctionLiteral {
+                parameters: vec![Parameter { name: "a".into(), domain: None }, Parameter { name: "b".into(), domain: None }],
+                body: Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("a".into()))),
+                    operator: Operator(Token::Plus, Infix),
+                    right: Some(Box::new(Expression::Identifier("b".into()))),
+                }),
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_anonymous_function_declaration() {
+    parse!(
+        "fn(x) = x ^ 2;",
+        vec![ExpressionStatement {
+            expression: Expression::FunctionLiteral {
+                parameters: vec![Parameter { name: "x".into(), domain: None }],
+                body: Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("x".into()))),
+                    operator: Operator(Token::Exponent, Infix),
+                    right: Some(Box::new(Expression::IntegerLiteral(2))),
+                }),
+            }
+        }]
+    );
+}
+
+bad_parsing!(test_function_literal_inside_operation, "1 + x => x;");
+bad_parsing!(test_anonymous_function_without_body, "fn(x);");
+bad_parsing!(test_function_literal_without_parameter_names, "(1, 2) => 3;");
+
+#[test]
+fn test_function_declaration_with_domains() {
+    parse!(
+        "fn f(x: R, n in N) -> R = x ^ n;",
+        vec![FunctionDeclaration {
+            identifier: "f".into(),
+            parameters: vec![
+                Parameter { name: "x".into(), domain: Some(Expression::Identifier("R".into())) },
+                Parameter { name: "n".into(), domain: Some(Expression::Identifier("N".into())) },
+            ],
+            codomain: Some(Expression::Identifier("R".into())),
+            expression: Expression::Combination {
+                left: Some(Box::new(Expression::Identifier("x".into()))),
+                operator: Operator(Token::Exponent, Infix),
+                right: Some(Box::new(Expression::Identifier("n".into()))),
+            }
+        }]
+    );
+}
+
+bad_parsing!(test_parameter_without_domain, "fn f(x: ) = x;");
+bad_parsing!(test_assignment_followed_by_assign, "let x = 2 = 3;");
+
+#[test]
+fn test_composition_binds_like_multiplication() {
+    parse!(
+        "f ∘ g + 2 * h;",
+        vec![ExpressionStatement {
+            expression: Expression::Combination {
+                left: Some(Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::Identifier("f".into()))),
+                    operator: Operator(Token::Compose, Infix),
+                    right: Some(Box::new(Expression::Identifier("g".into()))),
+                })),
+                operator: Operator(Token::Plus, Infix),
+                right: Some(Box::new(Expression::Combination {
+                    left: Some(Box::new(Expression::IntegerLiteral(2))),
+                    operator: Operator(Token::Multiply, Infix),
+                    right: Some(Box::new(Expression::Identifier("h".into()))),
+                })),
+            }
+        }]
+    );
+}
@@ -1,16 +1,22 @@
 // use super::{Environment, Expression::*, Object::*, Token, Operator, Location, Statement};
-use crate::ast::environment::Environment;
+use crate::ast::environment::{Environment, NumericMode};
 use crate::ast::expression::Expression::*;
 use crate::ast::operator::Operator;
 use crate::ast::location::Location;
 use crate::ast::object::Object::*;
 use crate::ast::statement::Statement;
 use crate::interpreter::token::Token;
+use crate::repl::exec;
 use std::mem;
 // PlusMinus, MinusPlus (once sets are implemented)
 
+// the output of a program, or the error it stops with
+fn run(source: &str) -> Result<String, String> {
+    exec(source, &mut Environment::new()).map_err(|err| err.to_string())
+}
+
 macro_rules! assert_evals {
-    ($token:expr, $right:expr, $obj:expr) => {
+    ($token:expr, $right:expr, $obj:expr) => {{
         // prefix expression
         assert_eq!(
             Environment::new().eval(&Combination {
@@ -20,8 +26,8 @@ macro_rules! assert_evals {
             }).unwrap(),
             $obj
         );
-    };
-    (postfix $left:expr, $token:expr, $obj:expr) => {
+    }};
+    (postfix $left:expr, $token:expr, $obj:expr) => {{
         // prefix expression
         assert_eq!(
             Environment::new().eval(&Combination {
@@ -31,8 +37,8 @@ macro_rules! assert_evals {
             }).unwrap(),
             $obj
         );
-    };
-    ($left:expr, $token:expr, $right:expr, $obj:expr) => {
+    }};
+    ($left:expr, $token:expr, $right:expr, $obj:expr) => {{
         // infix expression
         assert_eq!(
             Environment::new().eval(&Combination {
@@ -42,8 +48,8 @@ macro_rules! assert_evals {
             }).unwrap(),
             $obj
         );
-    };
-    ($left:expr, $token:expr, $right:expr, $obj:expr, tol=$tol:expr) => {
+    }};
+    ($left:expr, $token:expr, $right:expr, $obj:expr, tol=$tol:expr) => {{
         // equality with tolerance
         let val = Environment::new().eval(&Combination {
             left: Some(Box::new($left)),
@@ -53,7 +59,7 @@ macro_rules! assert_evals {
         assert!(
             Boolean((-$tol).unwrap() <= (val.clone() - $obj).unwrap()).and(&Boolean((val - $obj).unwrap() <= $tol)).unwrap() == Boolean(true)
         );
-    };
+    }};
 }
 
 #[test]
@@ -61,7 +67,7 @@ fn test_add_int_real() {
     assert_evals!(
         IntegerLiteral(2),
         Token::Plus,
-        RealLiteral(40.0),
+        RealLiteral("40.0".into()),
         Real(42.0)
     );
 }
@@ -78,13 +84,13 @@ fn test_add_int_int() {
 
 #[test]
 fn test_add_real_real() {
-    assert_evals!(RealLiteral(2.9), Token::Plus, RealLiteral(39.1), Real(42.0));
+    assert_evals!(RealLiteral("2.9".into()), Token::Plus, RealLiteral("39.1".into()), Real(42.0));
 }
 
 #[test]
 fn test_add_real_int() {
     assert_evals!(
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Token::Plus,
         IntegerLiteral(40),
         Real(42.0)
@@ -104,7 +110,7 @@ fn test_sub_int_int() {
 #[test]
 fn test_sub_real_int() {
     assert_evals!(
-        RealLiteral(40.0),
+        RealLiteral("40.0".into()),
         Token::Minus,
         IntegerLiteral(2),
         Real(38.0)
@@ -116,7 +122,7 @@ fn test_sub_int_real() {
     assert_evals!(
         IntegerLiteral(2),
         Token::Minus,
-        RealLiteral(40.0),
+        RealLiteral("40.0".into()),
         Real(-38.0)
     );
 }
@@ -124,9 +130,9 @@ fn test_sub_int_real() {
 #[test]
 fn test_sub_real_real() {
     assert_evals!(
-        RealLiteral(40.0),
+        RealLiteral("40.0".into()),
         Token::Minus,
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Real(38.0)
     );
 }
@@ -146,7 +152,7 @@ fn test_mult_int_real() {
     assert_evals!(
         IntegerLiteral(3),
         Token::Multiply,
-        RealLiteral(3.2),
+        RealLiteral("3.2".into()),
         Real(9.6),
         tol = Real(0.0001)
     );
@@ -155,7 +161,7 @@ fn test_mult_int_real() {
 #[test]
 fn test_mult_real_int() {
     assert_evals!(
-        RealLiteral(3.2),
+        RealLiteral("3.2".into()),
         Token::Multiply,
         IntegerLiteral(3),
         Real(9.6),
@@ -166,9 +172,9 @@ fn test_mult_real_int() {
 #[test]
 fn test_mult_real_real() {
     assert_evals!(
-        RealLiteral(3.0),
+        RealLiteral("3.0".into()),
         Token::Multiply,
-        RealLiteral(3.2),
+        RealLiteral("3.2".into()),
         Real(9.6),
         tol = Real(0.0001)
     );
@@ -229,7 +235,7 @@ fn test_eq_int_real() {
     assert_evals!(
         IntegerLiteral(2),
         Token::Equals,
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Boolean(true)
     )
 }
@@ -237,7 +243,7 @@ fn test_eq_int_real() {
 #[test]
 fn test_eq_real_int() {
     assert_evals!(
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Token::Equals,
         IntegerLiteral(2),
         Boolean(true)
@@ -247,9 +253,9 @@ fn test_eq_real_int() {
 #[test]
 fn test_eq_real_real() {
     assert_evals!(
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Token::Equals,
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Boolean(true)
     )
 }
@@ -299,7 +305,7 @@ fn test_ne_int_real() {
     assert_evals!(
         IntegerLiteral(2),
         Token::NotEquals,
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Boolean(false)
     )
 }
@@ -307,7 +313,7 @@ fn test_ne_int_real() {
 #[test]
 fn test_ne_real_int() {
     assert_evals!(
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Token::NotEquals,
         IntegerLiteral(2),
         Boolean(false)
@@ -317,9 +323,9 @@ fn test_ne_real_int() {
 #[test]
 fn test_ne_real_real() {
     assert_evals!(
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Token::NotEquals,
-        RealLiteral(2.0),
+        RealLiteral("2.0".into()),
         Boolean(false)
     )
 }
@@ -359,7 +365,7 @@ fn test_gt_int_real() {
     assert_evals!(
         IntegerLiteral(1),
         Token::GreaterThan,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(false)
     )
 }
@@ -367,7 +373,7 @@ fn test_gt_int_real() {
 #[test]
 fn test_gt_real_int() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::GreaterThan,
         IntegerLiteral(1),
         Boolean(false)
@@ -377,9 +383,9 @@ fn test_gt_real_int() {
 #[test]
 fn test_gt_real_real() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::GreaterThan,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(false)
     )
 }
@@ -399,7 +405,7 @@ fn test_gte_int_real() {
     assert_evals!(
         IntegerLiteral(1),
         Token::GreaterThanEquals,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(true)
     )
 }
@@ -407,7 +413,7 @@ fn test_gte_int_real() {
 #[test]
 fn test_gte_real_int() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::GreaterThanEquals,
         IntegerLiteral(1),
         Boolean(true)
@@ -417,9 +423,9 @@ fn test_gte_real_int() {
 #[test]
 fn test_gte_real_real() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::GreaterThanEquals,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(true)
     )
 }
@@ -439,7 +445,7 @@ fn test_lt_int_real() {
     assert_evals!(
         IntegerLiteral(1),
         Token::LessThan,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(false)
     )
 }
@@ -447,7 +453,7 @@ fn test_lt_int_real() {
 #[test]
 fn test_lt_real_int() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::LessThan,
         IntegerLiteral(1),
         Boolean(false)
@@ -457,9 +463,9 @@ fn test_lt_real_int() {
 #[test]
 fn test_lt_real_real() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::LessThan,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(false)
     )
 }
@@ -479,7 +485,7 @@ fn test_lte_int_real() {
     assert_evals!(
         IntegerLiteral(1),
         Token::LessThanEquals,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(true)
     )
 }
@@ -487,7 +493,7 @@ fn test_lte_int_real() {
 #[test]
 fn test_lte_real_int() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::LessThanEquals,
         IntegerLiteral(1),
         Boolean(true)
@@ -497,9 +503,9 @@ fn test_lte_real_int() {
 #[test]
 fn test_lte_real_real() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::LessThanEquals,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Boolean(true)
     )
 }
@@ -519,7 +525,7 @@ fn test_exp_int_real() {
     assert_evals!(
         IntegerLiteral(1),
         Token::Exponent,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Real(1.0)
     )
 }
@@ -527,7 +533,7 @@ fn test_exp_int_real() {
 #[test]
 fn test_exp_real_int() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::Exponent,
         IntegerLiteral(1),
         Real(1.0)
@@ -537,9 +543,9 @@ fn test_exp_real_int() {
 #[test]
 fn test_exp_real_real() {
     assert_evals!(
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Token::Exponent,
-        RealLiteral(1.0),
+        RealLiteral("1.0".into()),
         Real(1.0)
     )
 }
@@ -551,7 +557,7 @@ fn test_neg_int() {
 
 #[test]
 fn test_neg_real() {
-    assert_evals!(Token::Minus, RealLiteral(1.0), Real(-1.0))
+    assert_evals!(Token::Minus, RealLiteral("1.0".into()), Real(-1.0))
 }
 
 #[test]
@@ -716,9 +722,9 @@ fn test_set_literal_assignment_with_reals() {
     let stmt = Statement::Assignment {
         identifier: String::from("foobar"),
         expression: SetLiteral(vec![
-            RealLiteral(1.0),
-            RealLiteral(2.0),
-            RealLiteral(3.0),
+            RealLiteral("1.0".into()),
+            RealLiteral("2.0".into()),
+            RealLiteral("3.0".into()),
         ]),
     };
     let obj = env.eval_statement(&stmt).unwrap();
@@ -732,7 +738,7 @@ fn test_set_literal_with_different_types() {
     let stmt = Statement::Assignment {
         identifier: String::from("foobar"),
         expression: SetLiteral(vec![
-            RealLiteral(1.0),
+            RealLiteral("1.0".into()),
             BooleanLiteral(true),
         ]),
     };
@@ -812,13 +818,13 @@ fn test_set_union() {
     let stmt = Statement::ExpressionStatement {
         expression: Combination {
             left: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
             ]))),
             operator: Operator(Token::Union, Location::Infix),
             right: Some(Box::new(SetLiteral(vec![
-                RealLiteral(3.1),
-                RealLiteral(4.1),
+                RealLiteral("3.1".into()),
+                RealLiteral("4.1".into()),
             ]))),
         }
     };
@@ -832,13 +838,13 @@ fn test_set_difference() {
     let stmt = Statement::ExpressionStatement {
         expression: Combination {
             left: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
             ]))),
             operator: Operator(Token::SetDifference, Location::Infix),
             right: Some(Box::new(SetLiteral(vec![
-                RealLiteral(2.1),
-                RealLiteral(3.1),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
             ]))),
         }
     };
@@ -852,13 +858,13 @@ fn test_set_intersection() {
     let stmt = Statement::ExpressionStatement {
         expression: Combination {
             left: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
             ]))),
             operator: Operator(Token::Intersection, Location::Infix),
             right: Some(Box::new(SetLiteral(vec![
-                RealLiteral(2.1),
-                RealLiteral(3.1),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
             ]))),
         }
     };
@@ -872,15 +878,15 @@ fn test_set_symmetric_difference() {
     let stmt = Statement::ExpressionStatement {
         expression: Combination {
             left: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
-                RealLiteral(3.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
             ]))),
             operator: Operator(Token::SymmetricDifference, Location::Infix),
             right: Some(Box::new(SetLiteral(vec![
-                RealLiteral(2.1),
-                RealLiteral(3.1),
-                RealLiteral(4.1),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
+                RealLiteral("4.1".into()),
             ]))),
         }
     };
@@ -894,17 +900,17 @@ fn test_set_subset() {
     let stmt = Statement::ExpressionStatement {
         expression: Combination {
             left: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
-                RealLiteral(3.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
             ]))),
             operator: Operator(Token::LessThanEquals, Location::Infix),
             right: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
-                RealLiteral(3.1),
-                RealLiteral(4.1),
-                RealLiteral(5.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
+                RealLiteral("4.1".into()),
+                RealLiteral("5.1".into()),
             ]))),
         }
     };
@@ -918,17 +924,17 @@ fn test_set_proper_subset() {
     let stmt = Statement::ExpressionStatement {
         expression: Combination {
             left: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
-                RealLiteral(3.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
             ]))),
             operator: Operator(Token::LessThan, Location::Infix),
             right: Some(Box::new(SetLiteral(vec![
-                RealLiteral(1.1),
-                RealLiteral(2.1),
-                RealLiteral(3.1),
-                RealLiteral(4.1),
-                RealLiteral(5.1),
+                RealLiteral("1.1".into()),
+                RealLiteral("2.1".into()),
+                RealLiteral("3.1".into()),
+                RealLiteral("4.1".into()),
+                RealLiteral("5.1".into()),
             ]))),
         }
     };
@@ -943,4 +949,1749 @@ fn test_factorial() {
         Token::Bang,
         Integer(24)
     )
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_factorial_too_large() {
+    assert!(run("20001!;").unwrap_err().starts_with("OperatorError"));
+}
+
+#[test]
+fn test_integer_literal_too_large_for_i64() {
+    assert_eq!(run("12345678901234567890;").unwrap(), "12345678901234567890");
+    assert_eq!(run("12345678901234567890 mod 97;").unwrap(), "3 mod 97");
+    assert_eq!(run("-9223372036854775808 == -2 ^ 63;").unwrap(), "true");
+}
+fn fraction(numerator: i64, denominator: i64) -> crate::ast::expression::Expression {
+    Combination {
+        left: Some(Box::new(IntegerLiteral(numerator))),
+        operator: Operator(Token::Division, Location::Infix),
+        right: Some(Box::new(IntegerLiteral(denominator))),
+    }
+}
+
+#[test]
+fn test_div_int_int_is_rational() {
+    assert_evals!(
+        IntegerLiteral(2),
+        Token::Division,
+        IntegerLiteral(6),
+        Rational { numerator: 1, denominator: 3 }
+    )
+}
+
+#[test]
+fn test_div_int_int_is_integer_when_exact() {
+    assert_evals!(
+        IntegerLiteral(6),
+        Token::Division,
+        IntegerLiteral(-2),
+        Integer(-3)
+    )
+}
+
+#[test]
+fn test_div_by_zero_is_undefined() {
+    let obj = Environment::new().eval(&fraction(1, 0)).unwrap();
+    assert!(obj.is_undefined());
+}
+
+#[test]
+fn test_rational_sum_is_exact() {
+    assert_evals!(
+        Combination {
+            left: Some(Box::new(fraction(1, 3))),
+            operator: Operator(Token::Plus, Location::Infix),
+            right: Some(Box::new(fraction(1, 3))),
+        },
+        Token::Plus,
+        fraction(1, 3),
+        Integer(1)
+    )
+}
+
+#[test]
+fn test_mult_rational_rational() {
+    assert_evals!(
+        fraction(2, 3),
+        Token::Multiply,
+        fraction(3, 4),
+        Rational { numerator: 1, denominator: 2 }
+    )
+}
+
+#[test]
+fn test_add_rational_real() {
+    assert_evals!(
+        fraction(1, 4),
+        Token::Plus,
+        RealLiteral("0.5".into()),
+        Real(0.75)
+    )
+}
+
+#[test]
+fn test_exp_rational_int() {
+    assert_evals!(
+        fraction(2, 3),
+        Token::Exponent,
+        Combination {
+            left: None,
+            operator: Operator(Token::Minus, Location::Prefix),
+            right: Some(Box::new(IntegerLiteral(2))),
+        },
+        Rational { numerator: 9, denominator: 4 }
+    )
+}
+
+#[test]
+fn test_mod_rational_int() {
+    assert_evals!(
+        fraction(7, 2),
+        Token::Modulo,
+        IntegerLiteral(1),
+        Rational { numerator: 1, denominator: 2 }
+    )
+}
+
+#[test]
+fn test_lt_rational_real() {
+    assert_evals!(
+        fraction(1, 3),
+        Token::LessThan,
+        RealLiteral("0.5".into()),
+        Boolean(true)
+    )
+}
+
+#[test]
+fn test_abs_rational() {
+    let obj = Environment::new().eval(&AbsoluteValue(Box::new(fraction(-3, 4)))).unwrap();
+    assert_eq!(obj.to_string(), "3/4");
+}
+
+#[test]
+fn test_set_of_rationals() {
+    let obj = Environment::new().eval(&SetLiteral(vec![
+        fraction(1, 2),
+        fraction(1, 3),
+        fraction(2, 4),
+    ])).unwrap();
+    assert_eq!(obj.to_string(), "{ 1/3, 1/2 }");
+}
+
+fn imaginary(value: f64) -> crate::ast::expression::Expression {
+    Combination {
+        left: Some(Box::new(RealLiteral(value.to_string()))),
+        operator: Operator(Token::Imaginary, Location::Postfix),
+        right: None,
+    }
+}
+
+#[test]
+fn test_imaginary() {
+    assert_evals!(
+        postfix IntegerLiteral(3),
+        Token::Imaginary,
+        Complex { real: 0.0, imag: 3.0 }
+    )
+}
+
+#[test]
+fn test_add_int_complex() {
+    assert_evals!(
+        IntegerLiteral(1),
+        Token::Plus,
+        imaginary(2.0),
+        Complex { real: 1.0, imag: 2.0 }
+    )
+}
+
+#[test]
+fn test_mult_complex_complex_is_real() {
+    assert_evals!(
+        imaginary(2.0),
+        Token::Multiply,
+        imaginary(2.0),
+        Real(-4.0)
+    )
+}
+
+#[test]
+fn test_div_complex_int() {
+    assert_evals!(
+        imaginary(3.0),
+        Token::Division,
+        IntegerLiteral(2),
+        Complex { real: 0.0, imag: 1.5 }
+    )
+}
+
+#[test]
+fn test_exp_negative_square_root_is_complex() {
+    assert_evals!(
+        Combination {
+            left: None,
+            operator: Operator(Token::Minus, Location::Prefix),
+            right: Some(Box::new(IntegerLiteral(4))),
+        },
+        Token::Exponent,
+        RealLiteral("0.5".into()),
+        Complex { real: 0.0, imag: 2.0 }
+    )
+}
+
+#[test]
+fn test_abs_complex() {
+    let obj = Environment::new().eval(&AbsoluteValue(Box::new(Combination {
+        left: Some(Box::new(IntegerLiteral(3))),
+        operator: Operator(Token::Plus, Location::Infix),
+        right: Some(Box::new(imaginary(4.0))),
+    }))).unwrap();
+    assert_eq!(obj, Real(5.0));
+}
+
+#[test]
+fn test_complex_display() {
+    assert_eq!(Complex { real: 1.0, imag: 2.0 }.to_string(), "1 + 2i");
+    assert_eq!(Complex { real: 1.0, imag: -2.0 }.to_string(), "1 - 2i");
+    assert_eq!(Complex { real: 0.0, imag: 2.5 }.to_string(), "2.5i");
+}
+
+#[test]
+fn test_complex_numbers_are_not_ordered() {
+    assert_eq!(run("(1 + 2i) < (2 + 3i);").unwrap_err(), "TypeError: Cannot order 1 + 2i and 2 + 3i.");
+    for source in ["(1 + 2i) > 3;", "3 <= 2i;", "2i >= 2i;"] {
+        assert!(run(source).unwrap_err().starts_with("TypeError"), "{}", source);
+    }
+    assert_eq!(run("(1 + 2i) == (1 + 2i);").unwrap(), "true");
+}
+
+#[test]
+fn test_add_int_int_overflow_promotes() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(IntegerLiteral(i64::MAX))),
+        operator: Operator(Token::Plus, Location::Infix),
+        right: Some(Box::new(IntegerLiteral(1))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "9223372036854775808");
+}
+
+#[test]
+fn test_big_integer_demotes_when_it_fits() {
+    assert_evals!(
+        Combination {
+            left: Some(Box::new(IntegerLiteral(i64::MAX))),
+            operator: Operator(Token::Multiply, Location::Infix),
+            right: Some(Box::new(IntegerLiteral(2))),
+        },
+        Token::Division,
+        IntegerLiteral(2),
+        Integer(i64::MAX)
+    )
+}
+
+#[test]
+fn test_rational_with_big_parts_stays_exact() {
+    assert_eq!(run("let x = 1/9223372036854775807; x * x;").unwrap(), "1/85070591730234615847396907784232501249");
+    assert_eq!(run("let x = 1/9223372036854775807; x * x * 9223372036854775807;").unwrap(), "1/9223372036854775807");
+    assert_eq!(run("(10^30 + 1) / 7 * 7 == 10^30 + 1;").unwrap(), "true");
+    assert_eq!(run("-((10^30 + 1) / 7) < 0;").unwrap(), "true");
+    assert_eq!(run("((10^30 + 1) / 3) mod 7;").unwrap(), "3 mod 7");
+}
+
+#[test]
+fn test_large_factorial() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(IntegerLiteral(21))),
+        operator: Operator(Token::Bang, Location::Postfix),
+        right: None,
+    }).unwrap();
+    assert_eq!(obj.to_string(), "51090942171709440000");
+}
+
+#[test]
+fn test_exp_int_int_is_exact() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(IntegerLiteral(2))),
+        operator: Operator(Token::Exponent, Location::Infix),
+        right: Some(Box::new(IntegerLiteral(100))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "1267650600228229401496703205376");
+}
+
+#[test]
+fn test_mod_by_zero_is_undefined() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(IntegerLiteral(5))),
+        operator: Operator(Token::Modulo, Location::Infix),
+        right: Some(Box::new(IntegerLiteral(0))),
+    }).unwrap();
+    assert!(obj.is_undefined());
+}
+
+#[test]
+fn test_neg_int_min() {
+    let obj = Environment::new().eval(&Combination {
+        left: None,
+        operator: Operator(Token::Minus, Location::Prefix),
+        right: Some(Box::new(Combination {
+            left: None,
+            operator: Operator(Token::Minus, Location::Prefix),
+            right: Some(Box::new(IntegerLiteral(i64::MAX))),
+        })),
+    }).unwrap();
+    assert_eq!(obj, Integer(i64::MAX));
+}
+
+#[test]
+fn test_big_integer_in_set() {
+    let big = Combination {
+        left: Some(Box::new(IntegerLiteral(2))),
+        operator: Operator(Token::Exponent, Location::Infix),
+        right: Some(Box::new(IntegerLiteral(70))),
+    };
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(big.clone())),
+        operator: Operator(Token::In, Location::Infix),
+        right: Some(Box::new(SetLiteral(vec![IntegerLiteral(3), big, IntegerLiteral(1)]))),
+    }).unwrap();
+    assert_eq!(obj, Boolean(true));
+}
+
+#[test]
+fn test_decimal_mode_add() {
+    let obj = Environment::with_mode(NumericMode::Decimal).eval(&Combination {
+        left: Some(Box::new(Combination {
+            left: Some(Box::new(RealLiteral("0.1".into()))),
+            operator: Operator(Token::Plus, Location::Infix),
+            right: Some(Box::new(RealLiteral("0.2".into()))),
+        })),
+        operator: Operator(Token::Equals, Location::Infix),
+        right: Some(Box::new(RealLiteral("0.3".into()))),
+    }).unwrap();
+    assert_eq!(obj, Boolean(true));
+}
+
+#[test]
+fn test_decimal_mode_division_rounds() {
+    let obj = Environment::with_mode(NumericMode::Decimal).eval(&Combination {
+        left: Some(Box::new(RealLiteral("2.0".into()))),
+        operator: Operator(Token::Division, Location::Infix),
+        right: Some(Box::new(RealLiteral("3.0".into()))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "0.66666666666666666667");
+}
+
+#[test]
+fn test_decimal_mode_integer_power_is_exact() {
+    let obj = Environment::with_mode(NumericMode::Decimal).eval(&Combination {
+        left: Some(Box::new(RealLiteral("1.1".into()))),
+        operator: Operator(Token::Exponent, Location::Infix),
+        right: Some(Box::new(IntegerLiteral(3))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "1.331");
+}
+
+#[test]
+fn test_decimal_mode_fractional_power_goes_through_floats() {
+    let obj = Environment::with_mode(NumericMode::Decimal).eval(&Combination {
+        left: Some(Box::new(RealLiteral("2.0".into()))),
+        operator: Operator(Token::Exponent, Location::Infix),
+        right: Some(Box::new(RealLiteral("0.5".into()))),
+    }).unwrap();
+    // only as many digits as the f64 square root has
+    assert_eq!(obj.to_string(), "1.4142135623730951");
+}
+
+#[test]
+fn test_decimal_mode_literals_keep_their_digits() {
+    let eval = |text: &str| Environment::with_mode(NumericMode::Decimal).eval(&RealLiteral(text.into())).unwrap().to_string();
+    assert_eq!(eval("0.12345678901234567890123"), "0.12345678901234567890123");
+    assert_eq!(eval("1234567890.123456789"), "1234567890.123456789");
+    // too large for a decimal
+    assert_eq!(eval("123456789012345678901234567890.5"), Real(123456789012345678901234567890.5).to_string());
+}
+
+#[test]
+fn test_decimal_mode_compares_with_rationals() {
+    let obj = Environment::with_mode(NumericMode::Decimal).eval(&Combination {
+        left: Some(Box::new(RealLiteral("0.5".into()))),
+        operator: Operator(Token::Equals, Location::Infix),
+        right: Some(Box::new(fraction(1, 2))),
+    }).unwrap();
+    assert_eq!(obj, Boolean(true));
+}
+
+#[test]
+fn test_decimal_mode_divide_by_zero() {
+    let obj = Environment::with_mode(NumericMode::Decimal).eval(&Combination {
+        left: Some(Box::new(RealLiteral("1.5".into()))),
+        operator: Operator(Token::Division, Location::Infix),
+        right: Some(Box::new(RealLiteral("0.0".into()))),
+    }).unwrap();
+    assert!(obj.is_undefined());
+}
+
+#[test]
+fn test_index_tuple() {
+    assert_eq!(
+        Environment::new().eval(&Index {
+            collection: Box::new(TupleLiteral(vec![IntegerLiteral(1), RealLiteral("2.5".into())])),
+            index: Box::new(IntegerLiteral(1)),
+        }).unwrap(),
+        Real(2.5)
+    );
+}
+
+#[test]
+fn test_index_tuple_out_of_range() {
+    let obj = Environment::new().eval(&Index {
+        collection: Box::new(TupleLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)])),
+        index: Box::new(IntegerLiteral(2)),
+    }).unwrap();
+    assert!(obj.is_undefined());
+}
+
+#[test]
+fn test_index_tuple_with_real() {
+    assert!(Environment::new().eval(&Index {
+        collection: Box::new(TupleLiteral(vec![IntegerLiteral(1)])),
+        index: Box::new(RealLiteral("0.0".into())),
+    }).is_err());
+}
+
+#[test]
+fn test_tuple_ordering_is_lexicographic() {
+    assert_evals!(
+        TupleLiteral(vec![IntegerLiteral(1), IntegerLiteral(9)]),
+        Token::LessThan,
+        TupleLiteral(vec![IntegerLiteral(2), IntegerLiteral(0)]),
+        Boolean(true)
+    )
+}
+
+#[test]
+fn test_ordering_tuples_and_vectors_of_sets() {
+    for source in ["({1},) < ({2},);", "({1}, 2) < ({1}, 3);", "[{1}] < [{2}];", "{:1:} < {:1, 2:};"] {
+        assert!(run(source).unwrap_err().starts_with("TypeError: Cannot order"), "{}", source);
+    }
+    assert_eq!(run("({1}, 2) == ({1}, 2);").unwrap(), "true");
+}
+
+#[test]
+fn test_tuples_in_set() {
+    let pair = TupleLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]);
+    let obj = Environment::new().eval(&SetLiteral(vec![pair.clone(), pair.clone()])).unwrap();
+    assert_eq!(obj.to_string(), "{ (1, 2) }");
+}
+
+#[test]
+fn test_abs_tuple() {
+    assert_eq!(
+        Environment::new().eval(&AbsoluteValue(Box::new(TupleLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)])))).unwrap(),
+        Integer(2)
+    );
+}
+
+fn matrix(rows: Vec<Vec<i64>>) -> crate::ast::expression::Expression {
+    MatrixLiteral(rows.into_iter().map(|row| row.into_iter().map(IntegerLiteral).collect()).collect())
+}
+
+#[test]
+fn test_add_vector_vector() {
+    assert_evals!(
+        VectorLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]),
+        Token::Plus,
+        VectorLiteral(vec![IntegerLiteral(3), RealLiteral("0.5".into())]),
+        Vector { items: vec![Integer(4), Real(2.5)] }
+    )
+}
+
+#[test]
+fn test_add_vectors_of_different_lengths() {
+    assert!(Environment::new().eval(&Combination {
+        left: Some(Box::new(VectorLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]))),
+        operator: Operator(Token::Plus, Location::Infix),
+        right: Some(Box::new(VectorLiteral(vec![IntegerLiteral(1)]))),
+    }).is_err());
+}
+
+#[test]
+fn test_mul_vector_vector_is_dot_product() {
+    assert_evals!(
+        VectorLiteral(vec![IntegerLiteral(1), IntegerLiteral(2), IntegerLiteral(3)]),
+        Token::Multiply,
+        VectorLiteral(vec![IntegerLiteral(4), IntegerLiteral(5), IntegerLiteral(6)]),
+        Integer(32)
+    )
+}
+
+#[test]
+fn test_mul_int_matrix() {
+    assert_evals!(
+        IntegerLiteral(2),
+        Token::Multiply,
+        matrix(vec![vec![1, 2], vec![3, 4]]),
+        Matrix { rows: vec![vec![Integer(2), Integer(4)], vec![Integer(6), Integer(8)]] }
+    )
+}
+
+#[test]
+fn test_mul_matrix_matrix() {
+    assert_evals!(
+        matrix(vec![vec![1, 2, 3]]),
+        Token::Multiply,
+        matrix(vec![vec![1], vec![2], vec![3]]),
+        Matrix { rows: vec![vec![Integer(14)]] }
+    )
+}
+
+#[test]
+fn test_mul_matrix_vector() {
+    assert_evals!(
+        matrix(vec![vec![1, 2], vec![3, 4]]),
+        Token::Multiply,
+        VectorLiteral(vec![IntegerLiteral(1), IntegerLiteral(-1)]),
+        Vector { items: vec![Integer(-1), Integer(-1)] }
+    )
+}
+
+#[test]
+fn test_transpose_matrix() {
+    assert_eq!(
+        Environment::new().eval(&Call {
+            function: Box::new(Identifier("transpose".into())),
+            arguments: vec![matrix(vec![vec![1, 2, 3], vec![4, 5, 6]])],
+        }).unwrap(),
+        Matrix { rows: vec![vec![Integer(1), Integer(4)], vec![Integer(2), Integer(5)], vec![Integer(3), Integer(6)]] }
+    );
+}
+
+#[test]
+fn test_determinant_is_exact() {
+    assert_eq!(
+        Environment::new().eval(&Call {
+            function: Box::new(Identifier("det".into())),
+            arguments: vec![matrix(vec![vec![0, 2, 1], vec![3, 1, 4], vec![1, 5, 9]])],
+        }).unwrap(),
+        Integer(-32)
+    );
+}
+
+#[test]
+fn test_inverse_matrix() {
+    let obj = Environment::new().eval(&Call {
+        function: Box::new(Identifier("inverse".into())),
+        arguments: vec![matrix(vec![vec![1, 2], vec![3, 4]])],
+    }).unwrap();
+    assert_eq!(obj.to_string(), "[[-2, 1], [3/2, -1/2]]");
+}
+
+#[test]
+fn test_inverse_singular_matrix() {
+    assert!(Environment::new().eval(&Call {
+        function: Box::new(Identifier("inverse".into())),
+        arguments: vec![matrix(vec![vec![1, 2], vec![2, 4]])],
+    }).unwrap().is_undefined());
+}
+
+#[test]
+fn test_matrix_builder() {
+    let generator = |variable: &str| crate::ast::generator::Generator {
+        variable: variable.into(),
+        set: SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]),
+    };
+    assert_eq!(
+        Environment::new().eval(&MatrixBuilder {
+            expression: Box::new(Combination {
+                left: Some(Box::new(Identifier("i".into()))),
+                operator: Operator(Token::Minus, Location::Infix),
+                right: Some(Box::new(Identifier("j".into()))),
+            }),
+            generators: vec![generator("i"), generator("j")],
+        }).unwrap(),
+        Matrix { rows: vec![vec![Integer(0), Integer(-1)], vec![Integer(1), Integer(0)]] }
+    );
+}
+
+fn evens() -> crate::ast::expression::Expression {
+    crate::ast::expression::Expression::SetBuilder {
+        variable: "i".into(),
+        parent_set: Box::new(SetLiteral((1..=6).map(IntegerLiteral).collect())),
+        conditions: vec![Combination {
+            left: Some(Box::new(Combination {
+                left: Some(Box::new(Identifier("i".into()))),
+                operator: Operator(Token::Modulo, Location::Infix),
+                right: Some(Box::new(IntegerLiteral(2))),
+            })),
+            operator: Operator(Token::Equals, Location::Infix),
+            right: Some(Box::new(IntegerLiteral(0))),
+        }],
+    }
+}
+
+#[test]
+fn test_in_set_builder() {
+    assert_evals!(IntegerLiteral(4), Token::In, evens(), Boolean(true));
+    assert_evals!(IntegerLiteral(3), Token::In, evens(), Boolean(false));
+    assert_evals!(IntegerLiteral(8), Token::In, evens(), Boolean(false));
+}
+
+#[test]
+fn test_abs_set_builder() {
+    assert_eq!(Environment::new().eval(&AbsoluteValue(Box::new(evens()))).unwrap(), Integer(3));
+}
+
+#[test]
+fn test_union_set_builder() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(evens())),
+        operator: Operator(Token::Union, Location::Infix),
+        right: Some(Box::new(SetLiteral(vec![IntegerLiteral(1)]))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "{ 1, 2, 4, 6 }");
+}
+
+#[test]
+fn test_set_builder_condition_must_be_boolean() {
+    let builder = crate::ast::expression::Expression::SetBuilder {
+        variable: "i".into(),
+        parent_set: Box::new(SetLiteral(vec![IntegerLiteral(1)])),
+        conditions: vec![Identifier("i".into())],
+    };
+    assert!(Environment::new().eval(&Combination {
+        left: Some(Box::new(IntegerLiteral(1))),
+        operator: Operator(Token::In, Location::Infix),
+        right: Some(Box::new(builder)),
+    }).is_err());
+}
+
+#[test]
+fn test_set_comprehension() {
+    let obj = Environment::new().eval(&SetComprehension {
+        expression: Box::new(Combination {
+            left: Some(Box::new(Identifier("x".into()))),
+            operator: Operator(Token::Exponent, Location::Infix),
+            right: Some(Box::new(IntegerLiteral(2))),
+        }),
+        generators: vec![crate::ast::generator::Generator {
+            variable: "x".into(),
+            set: SetLiteral(vec![IntegerLiteral(-1), IntegerLiteral(1), IntegerLiteral(2)]),
+        }],
+    }).unwrap();
+    assert_eq!(obj.to_string(), "{ 1, 4 }");
+}
+
+#[test]
+fn test_set_comprehension_with_two_generators() {
+    let generator = |variable: &str, values: Vec<i64>| crate::ast::generator::Generator {
+        variable: variable.into(),
+        set: SetLiteral(values.into_iter().map(IntegerLiteral).collect()),
+    };
+    let obj = Environment::new().eval(&SetComprehension {
+        expression: Box::new(Combination {
+            left: Some(Box::new(Identifier("x".into()))),
+            operator: Operator(Token::Plus, Location::Infix),
+            right: Some(Box::new(Identifier("y".into()))),
+        }),
+        generators: vec![generator("x", vec![1, 2]), generator("y", vec![10, 20])],
+    }).unwrap();
+    assert_eq!(obj.to_string(), "{ 11, 12, 21, 22 }");
+}
+
+#[test]
+fn test_set_comprehension_with_mixed_types() {
+    assert!(Environment::new().eval(&SetComprehension {
+        expression: Box::new(PiecewiseBlock(vec![
+            (BooleanLiteral(true), Combination {
+                left: Some(Box::new(Identifier("x".into()))),
+                operator: Operator(Token::Equals, Location::Infix),
+                right: Some(Box::new(IntegerLiteral(1))),
+            }),
+            (Identifier("x".into()), BooleanLiteral(true)),
+        ])),
+        generators: vec![crate::ast::generator::Generator {
+            variable: "x".into(),
+            set: SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]),
+        }],
+    }).is_err());
+}
+
+#[test]
+fn test_in_number_sets() {
+    assert_evals!(IntegerLiteral(3), Token::In, Identifier("Z".into()), Boolean(true));
+    assert_evals!(RealLiteral("2.5".into()), Token::In, Identifier("Z".into()), Boolean(false));
+    assert_evals!(IntegerLiteral(-1), Token::In, Identifier("N".into()), Boolean(false));
+    assert_evals!(IntegerLiteral(0), Token::In, Identifier("N".into()), Boolean(true));
+    assert_evals!(fraction(1, 2), Token::In, Identifier("Q".into()), Boolean(true));
+    assert_evals!(RealLiteral("2.5".into()), Token::In, Identifier("R".into()), Boolean(true));
+    assert_evals!(imaginary(1.0), Token::In, Identifier("R".into()), Boolean(false));
+    assert_evals!(imaginary(1.0), Token::In, Identifier("C".into()), Boolean(true));
+}
+
+#[test]
+fn test_number_set_subsets() {
+    assert_evals!(Identifier("N".into()), Token::LessThan, Identifier("Z".into()), Boolean(true));
+    assert_evals!(Identifier("Q".into()), Token::LessThan, Identifier("R".into()), Boolean(true));
+    assert_evals!(Identifier("R".into()), Token::LessThanEquals, Identifier("Q".into()), Boolean(false));
+    assert_evals!(
+        SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]),
+        Token::LessThanEquals,
+        Identifier("N".into()),
+        Boolean(true)
+    );
+    assert_evals!(
+        SetLiteral(vec![IntegerLiteral(-1), IntegerLiteral(2)]),
+        Token::LessThanEquals,
+        Identifier("N".into()),
+        Boolean(false)
+    );
+}
+
+#[test]
+fn test_complement() {
+    let complement = Combination {
+        left: None,
+        operator: Operator(Token::SetDifference, Location::Prefix),
+        right: Some(Box::new(SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]))),
+    };
+    assert_evals!(IntegerLiteral(3), Token::In, complement.clone(), Boolean(true));
+    assert_evals!(IntegerLiteral(2), Token::In, complement.clone(), Boolean(false));
+    assert_evals!(RealLiteral("0.5".into()), Token::In, complement, Boolean(false));
+}
+
+#[test]
+fn test_difference_of_number_set() {
+    let difference = Combination {
+        left: Some(Box::new(Identifier("Z".into()))),
+        operator: Operator(Token::SetDifference, Location::Infix),
+        right: Some(Box::new(Identifier("N".into()))),
+    };
+    assert_evals!(IntegerLiteral(-3), Token::In, difference.clone(), Boolean(true));
+    assert_evals!(IntegerLiteral(3), Token::In, difference, Boolean(false));
+}
+
+#[test]
+fn test_intersection_with_number_set() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(SetLiteral(vec![IntegerLiteral(-1), IntegerLiteral(1)]))),
+        operator: Operator(Token::Intersection, Location::Infix),
+        right: Some(Box::new(Identifier("N".into()))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "{ 1 }");
+}
+
+#[test]
+fn test_abs_number_set() {
+    assert!(Environment::new().eval(&AbsoluteValue(Box::new(Identifier("N".into())))).is_err());
+}
+
+fn range(first: i64, second: Option<i64>, last: i64) -> crate::ast::expression::Expression {
+    RangeLiteral {
+        first: Box::new(IntegerLiteral(first)),
+        second: second.map(|second| Box::new(IntegerLiteral(second))),
+        last: Box::new(IntegerLiteral(last)),
+    }
+}
+
+#[test]
+fn test_range() {
+    let obj = Environment::new().eval(&range(1, Some(3), 100)).unwrap();
+    assert_eq!(obj, Range { start: 1, end: 99, step: 2 });
+    assert_eq!(obj.to_string(), "{ 1, 3, ..., 99 }");
+    assert_evals!(IntegerLiteral(7), Token::In, range(1, Some(3), 100), Boolean(true));
+    assert_evals!(IntegerLiteral(8), Token::In, range(1, Some(3), 100), Boolean(false));
+    assert_evals!(IntegerLiteral(101), Token::In, range(1, Some(3), 100), Boolean(false));
+}
+
+#[test]
+fn test_descending_range() {
+    let obj = Environment::new().eval(&range(10, Some(7), 0)).unwrap();
+    assert_eq!(obj, Range { start: 1, end: 10, step: 3 });
+}
+
+#[test]
+fn test_short_range_is_a_set() {
+    let obj = Environment::new().eval(&range(1, None, 2)).unwrap();
+    assert_eq!(obj.to_string(), "{ 1, 2 }");
+    let obj = Environment::new().eval(&range(1, None, 0)).unwrap();
+    assert_eq!(obj.to_string(), "{  }");
+}
+
+#[test]
+fn test_bad_range() {
+    assert!(Environment::new().eval(&range(1, Some(1), 10)).is_err());
+    assert!(Environment::new().eval(&RangeLiteral {
+        first: Box::new(RealLiteral("1.5".into())),
+        second: None,
+        last: Box::new(IntegerLiteral(10)),
+    }).is_err());
+}
+
+#[test]
+fn test_range_closed_forms() {
+    let env = Environment::new();
+    assert_eq!(env.eval(&AbsoluteValue(Box::new(range(1, None, 1_000_000_000)))).unwrap(), Integer(1_000_000_000));
+    let sum = env.eval(&Call {
+        function: Box::new(Identifier("sum".into())),
+        arguments: vec![range(1, None, 1_000_000_000)],
+    }).unwrap();
+    assert_eq!(sum, Integer(500_000_000_500_000_000));
+}
+
+#[test]
+fn test_range_with_set() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(range(1, None, 5))),
+        operator: Operator(Token::Intersection, Location::Infix),
+        right: Some(Box::new(SetLiteral(vec![IntegerLiteral(4), IntegerLiteral(6)]))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "{ 4 }");
+}
+
+#[test]
+fn test_range_equals_listed_set() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let hash = |obj: &crate::ast::object::Object| {
+        let mut hasher = DefaultHasher::new();
+        obj.hash(&mut hasher);
+        hasher.finish()
+    };
+    let env = Environment::new();
+    let (range, listed) = (env.eval(&range(1, None, 5)).unwrap(), env.eval(&SetLiteral((1..=5).map(IntegerLiteral).collect())).unwrap());
+    assert_eq!(range, listed);
+    assert_eq!(hash(&range), hash(&listed));
+    assert_eq!(run("{1, ..., 5} == {1, 2, 3, 4};").unwrap(), "false");
+    assert_eq!(run("{ {1, ..., 5}, {1, 2}, {1, 2, 3, 4, 5}, {1, 3, ..., 9} };").unwrap(), "{ { 1, 2 }, { 1, ..., 5 }, { 1, 3, ..., 9 } }");
+    assert_eq!(run("{1} in { {1}, {1, ..., 4} };").unwrap(), "true");
+}
+
+fn multiset(members: Vec<i64>) -> crate::ast::expression::Expression {
+    MultisetLiteral(members.into_iter().map(IntegerLiteral).collect())
+}
+
+#[test]
+fn test_multiset_keeps_duplicates() {
+    let obj = Environment::new().eval(&multiset(vec![2, 1, 2])).unwrap();
+    assert_eq!(obj.to_string(), "{: 1, 2, 2 :}");
+    assert_eq!(obj.abs().unwrap(), Integer(3));
+}
+
+#[test]
+fn test_absolute_values_in_set_literals() {
+    assert_eq!(run("{1, |-2|};").unwrap(), "{ 1, 2 }");
+    assert_eq!(run("{|-2|, 3};").unwrap(), "{ 2, 3 }");
+    assert_eq!(run("{: |-2|, 2 :};").unwrap(), "{: 2, 2 :}");
+}
+
+#[test]
+fn test_multiset_with_different_types() {
+    assert!(Environment::new().eval(&MultisetLiteral(vec![IntegerLiteral(1), BooleanLiteral(true)])).is_err());
+}
+
+#[test]
+fn test_multiset_operations() {
+    let operation = |token| Environment::new().eval(&Combination {
+        left: Some(Box::new(multiset(vec![1, 1, 2]))),
+        operator: Operator(token, Location::Infix),
+        right: Some(Box::new(multiset(vec![1, 2, 2, 3]))),
+    }).unwrap().to_string();
+    assert_eq!(operation(Token::Union), "{: 1, 1, 2, 2, 3 :}");
+    assert_eq!(operation(Token::Intersection), "{: 1, 2 :}");
+    assert_eq!(operation(Token::Plus), "{: 1, 1, 1, 2, 2, 2, 3 :}");
+    assert_eq!(operation(Token::SetDifference), "{: 1 :}");
+}
+
+#[test]
+fn test_multiset_with_set() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(multiset(vec![1, 1]))),
+        operator: Operator(Token::Plus, Location::Infix),
+        right: Some(Box::new(SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "{: 1, 1, 1, 2 :}");
+}
+
+#[test]
+fn test_multiset_builtins() {
+    let call = |name: &str, arguments| Environment::new().eval(&Call {
+        function: Box::new(Identifier(name.into())),
+        arguments,
+    }).unwrap();
+    assert_eq!(call("sum", vec![multiset(vec![1, 1, 2])]), Integer(4));
+    assert_eq!(call("product", vec![multiset(vec![2, 2, 3])]), Integer(12));
+    assert_eq!(call("max", vec![multiset(vec![2, 2, 3])]), Integer(3));
+    assert_eq!(call("count", vec![multiset(vec![2, 2, 3]), IntegerLiteral(2)]), Integer(2));
+    assert_eq!(call("count", vec![multiset(vec![2, 2, 3]), IntegerLiteral(4)]), Integer(0));
+}
+
+fn declare_suits(env: &mut Environment) {
+    env.eval_statement(&Statement::Assignment {
+        identifier: String::from("Suits"),
+        expression: Enumeration(vec!["hearts".into(), "spades".into(), "clubs".into()]),
+    }).unwrap();
+}
+
+#[test]
+fn test_enumeration() {
+    let mut env = Environment::new();
+    declare_suits(&mut env);
+    assert_eq!(env.eval(&Identifier("Suits".into())).unwrap().to_string(), "{ hearts, spades, clubs }");
+    assert_eq!(env.eval(&Identifier("spades".into())).unwrap().to_string(), "spades");
+    let contains = env.eval(&Combination {
+        left: Some(Box::new(Identifier("clubs".into()))),
+        operator: Operator(Token::In, Location::Infix),
+        right: Some(Box::new(Identifier("Suits".into()))),
+    }).unwrap();
+    assert_eq!(contains, Boolean(true));
+}
+
+#[test]
+fn test_atom_equality() {
+    let mut env = Environment::new();
+    declare_suits(&mut env);
+    let equals = |left: &str, right: &str| env.eval(&Combination {
+        left: Some(Box::new(Identifier(left.into()))),
+        operator: Operator(Token::Equals, Location::Infix),
+        right: Some(Box::new(Identifier(right.into()))),
+    }).unwrap();
+    assert_eq!(equals("hearts", "hearts"), Boolean(true));
+    assert_eq!(equals("hearts", "clubs"), Boolean(false));
+}
+
+#[test]
+fn test_set_of_bound_names_is_not_an_enumeration() {
+    let mut env = Environment::new();
+    env.eval_statement(&Statement::Assignment {
+        identifier: String::from("x"),
+        expression: IntegerLiteral(2),
+    }).unwrap();
+    assert_eq!(env.eval(&Enumeration(vec!["x".into()])).unwrap().to_string(), "{ 2 }");
+}
+
+#[test]
+fn test_enumerations_are_only_declared_by_let() {
+    assert_eq!(run("{ foo, bar };").unwrap_err(), "NameError: foo");
+    assert_eq!(run("let S = { hearts, spades }; let T = { hearts, clubs };").unwrap_err(), "NameError: Cannot re-declare value `hearts`.");
+    assert_eq!(run("let S = { hearts, spades }; let T = { spades, hearts }; T == S;").unwrap(), "true");
+}
+
+#[test]
+fn test_enumeration_with_duplicate_names() {
+    assert!(Environment::new().eval(&Enumeration(vec!["a".into(), "a".into()])).is_err());
+}
+
+fn call(name: &str, arguments: Vec<crate::ast::expression::Expression>) -> crate::ast::object::Object {
+    Environment::new().eval(&Call {
+        function: Box::new(Identifier(name.into())),
+        arguments,
+    }).unwrap()
+}
+
+// edges are (u, v) pairs or (u, v, weight) triples of integers
+fn graph(kind: &str, nodes: Vec<i64>, edges: Vec<Vec<i64>>) -> crate::ast::expression::Expression {
+    Call {
+        function: Box::new(Identifier(kind.into())),
+        arguments: vec![
+            SetLiteral(nodes.into_iter().map(IntegerLiteral).collect()),
+            SetLiteral(edges.into_iter().map(|edge| TupleLiteral(edge.into_iter().map(IntegerLiteral).collect())).collect()),
+        ],
+    }
+}
+
+#[test]
+fn test_graph_display() {
+    let obj = Environment::new().eval(&graph("graph", vec![1, 2, 3], vec![vec![2, 1], vec![2, 3, 4]])).unwrap();
+    assert_eq!(obj.to_string(), "graph({ 1, 2, 3 }, { (1, 2), (2, 3, 4) })");
+}
+
+#[test]
+fn test_graphs_are_not_ordered() {
+    assert_eq!(run("let g = graph({1}, {}); g < g;").unwrap_err(), "TypeError: Cannot order graph({ 1 }, {  }) and graph({ 1 }, {  }).");
+    assert!(run("let g = graph({1}, {}); g >= g;").unwrap_err().starts_with("TypeError"));
+    assert_eq!(run("let g = graph({1}, {}); g == g;").unwrap(), "true");
+}
+
+#[test]
+fn test_graph_edge_outside_nodes() {
+    assert!(Environment::new().eval(&graph("graph", vec![1, 2], vec![vec![1, 3]])).is_err());
+}
+
+#[test]
+fn test_graph_neighbours_and_degree() {
+    let undirected = graph("graph", vec![1, 2, 3], vec![vec![1, 2], vec![2, 3]]);
+    let directed = graph("digraph", vec![1, 2, 3], vec![vec![1, 2], vec![2, 3]]);
+    assert_eq!(call("neighbours", vec![undirected.clone(), IntegerLiteral(2)]).to_string(), "{ 1, 3 }");
+    assert_eq!(call("neighbours", vec![directed.clone(), IntegerLiteral(2)]).to_string(), "{ 3 }");
+    assert_eq!(call("degree", vec![undirected, IntegerLiteral(1)]), Integer(1));
+    assert_eq!(call("degree", vec![directed, IntegerLiteral(2)]), Integer(2));
+}
+
+#[test]
+fn test_graph_traversal() {
+    let tree = graph("graph", vec![1, 2, 3, 4, 5], vec![vec![1, 2], vec![1, 3], vec![2, 4], vec![3, 5]]);
+    assert_eq!(call("bfs", vec![tree.clone(), IntegerLiteral(1)]).to_string(), "(1, 2, 3, 4, 5)");
+    assert_eq!(call("dfs", vec![tree, IntegerLiteral(1)]).to_string(), "(1, 2, 4, 3, 5)");
+    let directed = graph("digraph", vec![1, 2, 3], vec![vec![1, 2], vec![2, 3]]);
+    assert_eq!(call("reachable", vec![directed.clone(), IntegerLiteral(1), IntegerLiteral(3)]), Boolean(true));
+    assert_eq!(call("reachable", vec![directed, IntegerLiteral(3), IntegerLiteral(1)]), Boolean(false));
+}
+
+#[test]
+fn test_graph_shortest_path() {
+    let weighted = graph("graph", vec![1, 2, 3, 4], vec![vec![1, 2, 1], vec![2, 4, 5], vec![1, 3, 2], vec![3, 4, 1]]);
+    assert_eq!(call("shortest_path", vec![weighted, IntegerLiteral(1), IntegerLiteral(4)]).to_string(), "(1, 3, 4)");
+    let disconnected = graph("graph", vec![1, 2, 3], vec![vec![1, 2]]);
+    assert_eq!(mem::discriminant(&call("shortest_path", vec![disconnected, IntegerLiteral(1), IntegerLiteral(3)])), mem::discriminant(&Undefined));
+}
+
+#[test]
+fn test_graph_components() {
+    let obj = call("components", vec![graph("graph", vec![1, 2, 3, 4, 5], vec![vec![1, 2], vec![3, 4]])]);
+    assert_eq!(obj.to_string(), "{ { 1, 2 }, { 3, 4 }, { 5 } }");
+}
+
+#[test]
+fn test_graph_cycles() {
+    let edges = vec![vec![1, 2], vec![2, 3], vec![1, 3]];
+    assert_eq!(call("has_cycle", vec![graph("graph", vec![1, 2, 3], edges.clone())]), Boolean(true));
+    assert_eq!(call("has_cycle", vec![graph("digraph", vec![1, 2, 3], edges)]), Boolean(false));
+    assert_eq!(call("has_cycle", vec![graph("digraph", vec![1, 2], vec![vec![1, 2], vec![2, 1]])]), Boolean(true));
+}
+
+fn interval(start: i64, end: i64, start_closed: bool, end_closed: bool) -> crate::ast::expression::Expression {
+    IntervalLiteral {
+        start: Box::new(IntegerLiteral(start)),
+        end: Box::new(IntegerLiteral(end)),
+        start_closed,
+        end_closed,
+    }
+}
+
+#[test]
+fn test_in_interval() {
+    assert_evals!(IntegerLiteral(0), Token::In, interval(0, 1, true, false), Boolean(true));
+    assert_evals!(IntegerLiteral(1), Token::In, interval(0, 1, true, false), Boolean(false));
+    assert_evals!(RealLiteral("0.5".into()), Token::In, interval(0, 1, false, false), Boolean(true));
+    assert_evals!(fraction(1, 3), Token::In, interval(0, 1, false, true), Boolean(true));
+}
+
+#[test]
+fn test_infinite_interval() {
+    let obj = Environment::new().eval(&IntervalLiteral {
+        start: Box::new(Combination {
+            left: None,
+            operator: Operator(Token::Minus, Location::Prefix),
+            right: Some(Box::new(Identifier("inf".into()))),
+        }),
+        end: Box::new(IntegerLiteral(0)),
+        start_closed: true,
+        end_closed: true,
+    }).unwrap();
+    assert_eq!(obj.to_string(), "(-inf .. 0]");
+}
+
+#[test]
+fn test_interval_algebra() {
+    let operation = |left, token, right| Environment::new().eval(&Combination {
+        left: Some(Box::new(left)),
+        operator: Operator(token, Location::Infix),
+        right: Some(Box::new(right)),
+    }).unwrap().to_string();
+    assert_eq!(operation(interval(0, 1, true, false), Token::Union, interval(1, 2, true, false)), "[0 .. 2)");
+    assert_eq!(operation(interval(0, 1, true, false), Token::Union, interval(2, 3, false, true)), "[0 .. 1) \\/ (2 .. 3]");
+    assert_eq!(operation(interval(0, 2, true, false), Token::Intersection, interval(1, 3, false, true)), "(1 .. 2)");
+    assert_eq!(operation(interval(0, 3, true, false), Token::SetDifference, interval(1, 2, false, true)), "[0 .. 1] \\/ (2 .. 3)");
+    assert_eq!(operation(interval(0, 1, true, false), Token::Intersection, interval(1, 2, false, true)), "{  }");
+}
+
+#[test]
+fn test_interval_with_finite_set() {
+    let obj = Environment::new().eval(&Combination {
+        left: Some(Box::new(interval(0, 1, true, false))),
+        operator: Operator(Token::Union, Location::Infix),
+        right: Some(Box::new(SetLiteral(vec![IntegerLiteral(1)]))),
+    }).unwrap();
+    assert_eq!(obj.to_string(), "[0 .. 1]");
+}
+
+#[test]
+fn test_interval_subset() {
+    let subset = |left, right| Environment::new().eval(&Combination {
+        left: Some(Box::new(left)),
+        operator: Operator(Token::LessThanEquals, Location::Infix),
+        right: Some(Box::new(right)),
+    }).unwrap();
+    assert_eq!(subset(interval(0, 1, true, false), interval(0, 1, true, true)), Boolean(true));
+    assert_eq!(run("[0, 1) <= [0 .. 1];").unwrap(), "true");
+    assert_eq!(subset(interval(0, 1, true, false), interval(0, 1, false, true)), Boolean(false));
+}
+
+#[test]
+fn test_intervals_have_their_own_literal() {
+    assert_eq!(run("[0, 1) == [0 .. 1];").unwrap(), "false");
+    assert_eq!(run("|[0, 1)|;").unwrap(), "1");
+    assert_eq!(run("|[0 .. 1] \\/ [2, 5)|;").unwrap(), "4");
+    assert_eq!(run("|[0, inf)|;").unwrap(), "undefined");
+    assert_eq!(run("3 in (1 .. 5);").unwrap(), "true");
+    assert_eq!(run("[0..1];").unwrap(), "[0 .. 1]");
+    // an interval bound to a name is still one
+    assert_eq!(run("let I = [0 .. 1]; 0.5 in I;").unwrap(), "true");
+    assert_eq!(run("[0 .. 3] <= [1 .. 2];").unwrap(), "false");
+    assert_eq!(run("[1 .. 2] <= [0 .. 3];").unwrap(), "true");
+    // pairs written with commas are always vectors and tuples
+    assert_eq!(run("[0, 3] <= [1, 2];").unwrap(), "true");
+    assert!(run("0.5 in [0, 1];").is_err());
+    assert!(run("let t = (1, 5); 3 in t;").is_err());
+    assert_eq!(run("(1, 2) == (1, 2);").unwrap(), "true");
+    assert!(run("1 == true;").unwrap_err().starts_with("TypeError"));
+    assert_eq!(run("1 == undefined;").unwrap(), "false");
+}
+
+#[test]
+fn test_set_with_integers_and_reals() {
+    let set = |members| Environment::new().eval(&SetLiteral(members)).unwrap();
+    assert_eq!(set(vec![IntegerLiteral(1), RealLiteral("2.5".into())]).to_string(), "{ 1, 2.5 }");
+    assert_eq!(set(vec![RealLiteral("2.0".into()), IntegerLiteral(1), IntegerLiteral(2)]).to_string(), "{ 1, 2 }");
+    assert_eq!(set(vec![IntegerLiteral(1), RealLiteral("2.0".into())]), set(vec![RealLiteral("1.0".into()), IntegerLiteral(2)]));
+    assert_evals!(IntegerLiteral(2), Token::In, SetLiteral(vec![RealLiteral("2.0".into()), RealLiteral("3.5".into())]), Boolean(true));
+    assert_evals!(RealLiteral("3.0".into()), Token::In, SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(3)]), Boolean(true));
+    assert_evals!(IntegerLiteral(3), Token::In, SetLiteral(vec![RealLiteral("2.0".into()), RealLiteral("3.5".into())]), Boolean(false));
+    assert_evals!(
+        SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]),
+        Token::Union,
+        SetLiteral(vec![RealLiteral("2.0".into()), RealLiteral("2.5".into())]),
+        set(vec![IntegerLiteral(1), IntegerLiteral(2), RealLiteral("2.5".into())])
+    );
+}
+
+#[test]
+fn test_equal_numbers_hash_the_same() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let hash = |obj: crate::ast::object::Object| {
+        let mut hasher = DefaultHasher::new();
+        obj.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(Integer(2)), hash(Real(2.0)));
+    assert_eq!(hash(Rational { numerator: 1, denominator: 2 }), hash(Real(0.5)));
+    assert_eq!(hash(Real(-0.0)), hash(Integer(0)));
+    assert_eq!(hash(Real(9007199254740992.0)), hash(BigInteger(num_bigint::BigInt::from(2).pow(53))));
+}
+
+#[test]
+fn test_equal_intervals_hash_the_same() {
+    use crate::ast::interval::Interval;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let hash = |interval: Interval| {
+        let mut hasher = DefaultHasher::new();
+        interval.hash(&mut hasher);
+        hasher.finish()
+    };
+    let (half, exact) = (Interval::new(Real(0.5), Integer(1), true, true).unwrap(), Interval::new(Rational { numerator: 1, denominator: 2 }, Integer(1), true, true).unwrap());
+    assert_eq!(half, exact);
+    assert_eq!(hash(half), hash(exact));
+    assert_eq!(run("{ [1/2 .. 1], [0.5 .. 1] };").unwrap(), "{ [1/2 .. 1] }");
+}
+
+#[test]
+fn test_mixed_number_equality_is_transitive() {
+    let (a, b, c) = (Integer(9007199254740993), Real(9007199254740992.0), Integer(9007199254740992));
+    assert_eq!((a == b, b == c, a == c), (false, true, false));
+    assert!(a > b && b == c);
+    assert_eq!(run("let a = 9007199254740993; let b = 9007199254740992.0; let c = 9007199254740992; {a, b, c} == {c, b, a};").unwrap(), "true");
+    assert_eq!(run("0.1 == 1 / 10;").unwrap(), "false");
+    assert_eq!(run("0.5 == 1 / 2;").unwrap(), "true");
+}
+
+#[test]
+fn test_types_of_objects() {
+    use crate::ast::types::Type;
+    let type_of = |expression| Type::of(&Environment::new().eval(&expression).unwrap()).to_string();
+    assert_eq!(type_of(SetLiteral(vec![IntegerLiteral(1), RealLiteral("2.5".into())])), "Set<Real>");
+    assert_eq!(type_of(SetLiteral(vec![])), "Set<Unknown>");
+    assert_eq!(type_of(SetLiteral(vec![SetLiteral(vec![]), SetLiteral(vec![BooleanLiteral(true)])])), "Set<Set<Boolean>>");
+    assert_eq!(type_of(TupleLiteral(vec![IntegerLiteral(1), BooleanLiteral(false)])), "(Integer, Boolean)");
+    assert_eq!(type_of(Identifier("Q".into())), "SymbolicSet<Rational>");
+    assert_eq!(type_of(Identifier("max".into())), "Function(1)");
+    assert_eq!(type_of(VectorLiteral(vec![IntegerLiteral(1), RealLiteral("2.5".into())])), "Vector<Real>");
+    assert_eq!(type_of(VectorLiteral(vec![IntegerLiteral(1), BooleanLiteral(true)])), "Vector<Mixed>");
+    assert_eq!(Type::Integer.join(&Type::Rational), Some(Type::Rational));
+    assert_eq!(Type::Set(Box::new(Type::Integer)).join(&Type::Set(Box::new(Type::Boolean))), None);
+}
+
+#[test]
+fn test_nested_sets_with_different_types() {
+    let set = SetLiteral;
+    assert!(Environment::new().eval(&set(vec![set(vec![IntegerLiteral(1)]), set(vec![BooleanLiteral(true)])])).is_err());
+    assert_eq!(
+        Environment::new().eval(&set(vec![set(vec![IntegerLiteral(1)]), set(vec![RealLiteral("2.5".into())])])).unwrap().to_string(),
+        "{ { 1 }, { 2.5 } }"
+    );
+    assert!(Environment::new().eval(&Combination {
+        left: Some(Box::new(BooleanLiteral(true))),
+        operator: Operator(Token::In, Location::Infix),
+        right: Some(Box::new(set(vec![IntegerLiteral(1)]))),
+    }).is_err());
+}
+
+#[test]
+fn test_vectors_and_matrices_of_different_types() {
+    for source in ["{[1], [true]};", "{[[1]], [[true]]};", "{:[1], [true]:};", "{ [1, true], [true, 1] };", "[1, true] < [1, true];"] {
+        assert!(run(source).unwrap_err().starts_with("TypeError"), "{}", source);
+    }
+    assert_eq!(run("{[1], [2.5], []};").unwrap(), "{ [], [1], [2.5] }");
+    assert_eq!(run("[1, 2] < [1, 3];").unwrap(), "true");
+}
+
+#[test]
+fn test_listed_and_symbolic_sets_do_not_mix() {
+    for source in ["{ {1}, N };", "{ {1, 2}, [0, 1) };", "{ {1}, { x in Z: x > 0 } };", "{1} in { N };"] {
+        assert!(run(source).unwrap_err().starts_with("TypeError"), "{}", source);
+    }
+    assert_eq!(run("{ N, [0, 1), Z \\ {1}, Z \\ [0, 1), Z \\ {1}, N };").unwrap(), "{ N, [0 .. 1), Z \\ { 1 }, Z \\ [0 .. 1) }");
+    assert_eq!(run("N in { N, Z };").unwrap(), "true");
+}
+
+// coefficients are listed from the highest power down
+fn polynomial(coefficients: Vec<i64>) -> crate::ast::expression::Expression {
+    Call {
+        function: Box::new(Identifier("poly".into())),
+        arguments: vec![VectorLiteral(coefficients.into_iter().map(IntegerLiteral).collect())],
+    }
+}
+
+#[test]
+fn test_polynomial_display() {
+    let display = |coefficients| call("poly", vec![polynomial(coefficients)]).to_string();
+    assert_eq!(display(vec![1, -4, -12]), "x^2 - 4x - 12");
+    assert_eq!(display(vec![-1, 0, 1, 0]), "-x^3 + x");
+    assert_eq!(display(vec![0, 0, 5]), "5");
+    assert_eq!(display(vec![0]), "0");
+}
+
+#[test]
+fn test_polynomial_from_function() {
+    let mut env = Environment::new();
+    env.eval_statement(&Statement::FunctionDeclaration {
+        identifier: "f".into(),
+        parameters: vec![crate::ast::parameter::Parameter { name: "t".into(), domain: None }],
+        codomain: None,
+        expression: Combination {
+            left: Some(Box::new(Combination {
+                left: Some(Box::new(Identifier("t".into()))),
+                operator: Operator(Token::Exponent, Location::Infix),
+                right: Some(Box::new(IntegerLiteral(2))),
+            })),
+            operator: Operator(Token::Division, Location::Infix),
+            right: Some(Box::new(IntegerLiteral(2))),
+        },
+    }).unwrap();
+    let obj = env.eval(&Call {
+        function: Box::new(Identifier("poly".into())),
+        arguments: vec![Identifier("f".into())],
+    }).unwrap();
+    assert_eq!(obj.to_string(), "(1/2)t^2");
+}
+
+#[test]
+fn test_polynomial_from_function_literal() {
+    assert_eq!(run("poly(fn(x) = x^2 - 4x - 12);").unwrap(), "x^2 - 4x - 12");
+    assert_eq!(run("let f = fn(a, b) = a * b; f(2, 3);").unwrap(), "6");
+}
+
+#[test]
+fn test_polynomial_arithmetic() {
+    let operation = |left, token, right| Environment::new().eval(&Combination {
+        left: Some(Box::new(left)),
+        operator: Operator(token, Location::Infix),
+        right: Some(Box::new(right)),
+    }).unwrap().to_string();
+    assert_eq!(operation(polynomial(vec![1, 2]), Token::Plus, polynomial(vec![1, 0, -3])), "x^2 + x - 1");
+    assert_eq!(operation(polynomial(vec![1, 2]), Token::Minus, polynomial(vec![1, 2])), "0");
+    assert_eq!(operation(polynomial(vec![1, 2]), Token::Multiply, polynomial(vec![1, -2])), "x^2 - 4");
+    assert_eq!(operation(polynomial(vec![1, 2]), Token::Exponent, IntegerLiteral(2)), "x^2 + 4x + 4");
+    assert_eq!(operation(polynomial(vec![1, 0, -1]), Token::Division, polynomial(vec![1, 1])), "x - 1");
+    assert_eq!(operation(polynomial(vec![1, 2]), Token::Division, IntegerLiteral(2)), "(1/2)x + 1");
+    assert!(Environment::new().eval(&Combination {
+        left: Some(Box::new(polynomial(vec![1, 0, -1]))),
+        operator: Operator(Token::Division, Location::Infix),
+        right: Some(Box::new(polynomial(vec![1, 2]))),
+    }).is_err());
+    // powers too large to work out exactly are refused rather than left to run
+    assert!(run("poly([1, 1]) ^ 100000;").unwrap_err().starts_with("OperatorError"));
+}
+
+#[test]
+fn test_polynomials_are_not_ordered() {
+    assert_eq!(run("poly([1, 2]) < poly([1, 3]);").unwrap_err(), "TypeError: Cannot order x + 2 and x + 3.");
+    assert!(run("poly([1, 2]) > 1;").unwrap_err().starts_with("TypeError"));
+    assert_eq!(run("poly([1, 2]) == poly([1, 2]);").unwrap(), "true");
+}
+
+#[test]
+fn test_polynomial_evaluation() {
+    let obj = Environment::new().eval(&Call {
+        function: Box::new(polynomial(vec![1, -4, -12])),
+        arguments: vec![IntegerLiteral(6)],
+    }).unwrap();
+    assert_eq!(obj, Integer(0));
+}
+
+#[test]
+fn test_polynomial_builtins() {
+    assert_eq!(call("divide", vec![polynomial(vec![1, 2, 3]), polynomial(vec![2, 1])]).to_string(), "((1/2)x + 3/4, 9/4)");
+    assert_eq!(call("derivative", vec![polynomial(vec![1, 2, 3, 4])]).to_string(), "3x^2 + 4x + 3");
+    assert_eq!(call("degree", vec![polynomial(vec![1, 2, 3, 4])]), Integer(3));
+    assert_eq!(run("degree;").unwrap(), "fn(g, v) = <builtin-function>; fn(p) = <builtin-function>");
+    assert!(run("degree(1, 2, 3);").unwrap_err().starts_with("OperatorError: No definition of"));
+    assert_eq!(call("gcd", vec![polynomial(vec![1, 0, -1]), polynomial(vec![1, 2, 1])]).to_string(), "x + 1");
+}
+
+#[test]
+fn test_polynomial_roots() {
+    assert_eq!(call("roots", vec![polynomial(vec![1, -4, -12])]).to_string(), "{ -2, 6 }");
+    // (x - 1)^3 (2x + 1)
+    assert_eq!(call("roots", vec![polynomial(vec![2, -5, 3, 1, -1])]).to_string(), "{ -1/2, 1 }");
+    assert_eq!(call("roots", vec![polynomial(vec![1, 0, 1])]).to_string(), "{  }");
+    match call("roots", vec![polynomial(vec![1, 0, -2])]) {
+        Set { items, .. } => assert!(items.iter().all(|root| (root.as_real().unwrap().abs() - 2f64.sqrt()).abs() < 1e-12)),
+        obj => panic!("Expected a set of roots got {}.", obj),
+    }
+}
+
+fn with_units() -> Environment {
+    let mut env = Environment::new();
+    env.eval_statement(&Statement::Import { module: "units".into() }).unwrap();
+    env
+}
+
+fn quantity(value: i64, unit: &str) -> crate::ast::expression::Expression {
+    Combination {
+        left: Some(Box::new(IntegerLiteral(value))),
+        operator: Operator(Token::Multiply, Location::Infix),
+        right: Some(Box::new(Identifier(unit.into()))),
+    }
+}
+
+fn infix(left: crate::ast::expression::Expression, token: Token, right: crate::ast::expression::Expression) -> crate::ast::expression::Expression {
+    Combination {
+        left: Some(Box::new(left)),
+        operator: Operator(token, Location::Infix),
+        right: Some(Box::new(right)),
+    }
+}
+
+#[test]
+fn test_units_arithmetic() {
+    let env = with_units();
+    let eval = |expression| env.eval(&expression).unwrap().to_string();
+    assert_eq!(eval(infix(quantity(5, "km"), Token::Plus, quantity(300, "m"))), "(53/10) km");
+    assert_eq!(eval(infix(quantity(2, "m"), Token::Multiply, quantity(3, "s"))), "6 m*s");
+    assert_eq!(eval(infix(quantity(10, "m"), Token::Division, quantity(2, "s"))), "5 m/s");
+    assert_eq!(eval(infix(quantity(2, "m"), Token::Exponent, IntegerLiteral(2))), "4 m^2");
+    assert_eq!(eval(infix(IntegerLiteral(1), Token::Division, quantity(2, "s"))), "(1/2) s^-1");
+    // the dimensions cancel
+    assert_eq!(env.eval(&infix(quantity(3, "m"), Token::Division, quantity(1, "km"))).unwrap(), Rational { numerator: 3, denominator: 1000 });
+}
+
+#[test]
+fn test_units_with_different_dimensions() {
+    let env = with_units();
+    assert!(env.eval(&infix(quantity(3, "m"), Token::Plus, quantity(2, "s"))).is_err());
+    assert!(env.eval(&infix(quantity(3, "m"), Token::Minus, IntegerLiteral(2))).is_err());
+    assert!(env.eval(&infix(quantity(3, "m"), Token::As, Identifier("s".into()))).is_err());
+    assert!(env.eval(&infix(quantity(3, "m"), Token::Equals, quantity(3, "s"))).is_err());
+    assert!(env.eval(&SetLiteral(vec![quantity(1, "m"), quantity(1, "s")])).is_err());
+    assert_eq!(run("import units; 1 km < 2 s;").unwrap_err(), "OperatorError: Cannot compare 1 km and 2 s (their dimensions differ).");
+    assert!(run("import units; 1 km == 1;").is_err());
+    assert_eq!(run("import units; 1 km == 1000 m;").unwrap(), "true");
+}
+
+#[test]
+fn test_import_units_reports_clashes() {
+    assert_eq!(run("let s = 3; import units; 2 s;").unwrap_err(), "NameError: Cannot import units: `s` is already bound.");
+    assert_eq!(run("import units; import units; 2 s;").unwrap(), "2 s");
+}
+
+#[test]
+fn test_compound_units() {
+    assert_eq!(run("import units; 2 kg m;").unwrap(), "2 kg*m");
+    assert_eq!(run("import units; 2 kg m/s^2 as newton;").unwrap(), "2 N");
+}
+
+#[test]
+fn test_number_times_name_binds_tighter_than_division() {
+    assert_eq!(run("import units; 3 kg / 2 s;").unwrap(), "(3/2) kg/s");
+    assert_eq!(run("import units; 1/2 s;").unwrap(), "(1/2) s^-1");
+    assert_eq!(run("let x = 4; 1/2x;").unwrap(), "1/8");
+    assert_eq!(run("let x = 4; 1/2*x;").unwrap(), "2");
+    assert_eq!(run("let x = 4; 2x^2;").unwrap(), "32");
+    assert_eq!(run("let x = 4; -2x;").unwrap(), "-8");
+}
+
+#[test]
+fn test_unit_conversion() {
+    let env = with_units();
+    let eval = |expression| env.eval(&expression).unwrap().to_string();
+    assert_eq!(eval(infix(quantity(1500, "m"), Token::As, Identifier("km".into()))), "(3/2) km");
+    let speed = infix(quantity(72, "km"), Token::Division, Identifier("h".into()));
+    let unit = infix(Identifier("m".into()), Token::Division, Identifier("s".into()));
+    assert_eq!(eval(infix(speed, Token::As, unit)), "20 m/s");
+    assert_eq!(eval(infix(quantity(2, "kN"), Token::As, Identifier("newton".into()))), "2000 N");
+}
+
+#[test]
+fn test_quantities_compare_by_size() {
+    let env = with_units();
+    assert_eq!(env.eval(&infix(quantity(1, "km"), Token::Equals, quantity(1000, "m"))).unwrap(), Boolean(true));
+    assert_eq!(env.eval(&infix(quantity(3, "m"), Token::LessThan, quantity(2, "km"))).unwrap(), Boolean(true));
+    assert_eq!(env.eval(&SetLiteral(vec![quantity(1, "km"), quantity(1000, "m"), quantity(2, "m")])).unwrap().to_string(), "{ 2 m, 1 km }");
+}
+
+#[test]
+fn test_import_unknown_module() {
+    assert!(Environment::new().eval_statement(&Statement::Import { module: "foo".into() }).is_err());
+    // the number sets are not replaced by newtons and coulombs
+    assert_eq!(with_units().get("N"), Environment::new().get("N"));
+}
+
+fn enclosure(lo: f64, hi: f64) -> crate::ast::object::Object {
+    Enclosure(crate::ast::enclosure::Enclosure { lo, hi })
+}
+
+#[test]
+fn test_enclosure_arithmetic() {
+    assert_eq!((enclosure(1.0, 2.0) + enclosure(3.0, 4.0)).unwrap(), enclosure(4.0, 6.0));
+    assert_eq!((enclosure(1.0, 2.0) - enclosure(3.0, 4.0)).unwrap(), enclosure(-3.0, -1.0));
+    assert_eq!((enclosure(1.0, 2.0) * enclosure(-3.0, 4.0)).unwrap(), enclosure(-6.0, 8.0));
+    assert_eq!((enclosure(1.0, 2.0) / enclosure(4.0, 8.0)).unwrap(), enclosure(0.125, 0.5));
+    assert_eq!(mem::discriminant(&(enclosure(1.0, 2.0) / enclosure(-1.0, 1.0)).unwrap()), mem::discriminant(&Undefined));
+    assert_eq!(enclosure(-2.0, 3.0).pow(&Integer(2)).unwrap(), enclosure(0.0, 9.0));
+    assert_eq!(enclosure(-2.0, 3.0).pow(&Integer(3)).unwrap(), enclosure(-8.0, 27.0));
+    assert_eq!(enclosure(-5.0, 3.0).abs().unwrap(), enclosure(0.0, 5.0));
+    assert_eq!((enclosure(1.0, 2.0) + Integer(1)).unwrap(), enclosure(2.0, 3.0));
+}
+
+#[test]
+fn test_enclosure_rounds_outward() {
+    match (enclosure(0.1, 0.1) + enclosure(0.2, 0.2)).unwrap() {
+        Enclosure(sum) => {
+            assert!(sum.lo < sum.hi);
+            assert!(sum.lo <= 0.1 + 0.2 && 0.1 + 0.2 <= sum.hi);
+        },
+        obj => panic!("Expected an enclosure got {}.", obj),
+    }
+    // a third cannot be held exactly, so it is widened to the floats around it
+    match (Rational { numerator: 1, denominator: 3 } + enclosure(0.0, 0.0)).unwrap() {
+        Enclosure(third) => assert!(third.lo < 1.0 / 3.0 && 1.0 / 3.0 < third.hi),
+        obj => panic!("Expected an enclosure got {}.", obj),
+    }
+}
+
+#[test]
+fn test_enclosure_comparisons() {
+    let env = Environment::new();
+    let compare = |left, token, right| env.eval(&Combination {
+        left: Some(Box::new(left)),
+        operator: Operator(token, Location::Infix),
+        right: Some(Box::new(right)),
+    }).unwrap();
+    let literal = |lo, hi| Call {
+        function: Box::new(Identifier("enclosure".into())),
+        arguments: vec![IntegerLiteral(lo), IntegerLiteral(hi)],
+    };
+    assert_eq!(compare(literal(1, 2), Token::LessThan, literal(3, 4)), Boolean(true));
+    assert_eq!(compare(literal(3, 4), Token::LessThan, literal(1, 2)), Boolean(false));
+    assert_eq!(mem::discriminant(&compare(literal(1, 3), Token::LessThan, literal(2, 4))), mem::discriminant(&Undefined));
+    assert_eq!(compare(literal(1, 2), Token::LessThanEquals, IntegerLiteral(2)), Boolean(true));
+    assert_eq!(compare(literal(1, 2), Token::GreaterThan, IntegerLiteral(2)), Boolean(false));
+    assert_eq!(compare(literal(1, 2), Token::Equals, IntegerLiteral(3)), Boolean(false));
+    assert_eq!(compare(literal(1, 2), Token::NotEquals, IntegerLiteral(3)), Boolean(true));
+    assert_eq!(compare(literal(1, 1), Token::Equals, IntegerLiteral(1)), Boolean(true));
+    assert_eq!(mem::discriminant(&compare(literal(1, 2), Token::Equals, literal(1, 2))), mem::discriminant(&Undefined));
+}
+
+#[test]
+fn test_enclosure_builtins() {
+    match call("within", vec![RealLiteral("9.81".into()), RealLiteral("0.01".into())]) {
+        Enclosure(measurement) => assert!(measurement.lo <= 9.8 && 9.82 <= measurement.hi && measurement.hi - measurement.lo < 0.0201),
+        obj => panic!("Expected an enclosure got {}.", obj),
+    }
+    // bounds that floats hold exactly are not widened
+    assert_eq!(call("within", vec![RealLiteral("1.0".into()), RealLiteral("0.5".into())]).to_string(), "⟨0.5, 1.5⟩");
+    assert_eq!(call("within", vec![RealLiteral("0.1".into()), IntegerLiteral(0)]).to_string(), "⟨0.1, 0.1⟩");
+    // otherwise only the rounding of the sum widens them, by a single step
+    assert_eq!(call("within", vec![RealLiteral("1.0".into()), RealLiteral("0.1".into())]).to_string(), "⟨0.8999999999999999, 1.1⟩");
+    assert_eq!(call("within", vec![IntegerLiteral(1), IntegerLiteral(0)]).to_string(), "⟨1, 1⟩");
+    assert!(run("within(1, 0.1) == {1};").unwrap_err().starts_with("TypeError: Cannot compare"));
+    assert_eq!(call("enclosure", vec![IntegerLiteral(1), IntegerLiteral(2)]).to_string(), "⟨1, 2⟩");
+    let literal = Call {
+        function: Box::new(Identifier("enclosure".into())),
+        arguments: vec![IntegerLiteral(1), IntegerLiteral(2)],
+    };
+    assert_eq!(call("bounds", vec![literal]).to_string(), "(1, 2)");
+    assert!(Environment::new().eval(&Call {
+        function: Box::new(Identifier("within".into())),
+        arguments: vec![IntegerLiteral(1), IntegerLiteral(-1)],
+    }).is_err());
+}
+
+fn residue(value: i64, modulus: i64) -> crate::ast::expression::Expression {
+    infix(IntegerLiteral(value), Token::Mod, IntegerLiteral(modulus))
+}
+
+#[test]
+fn test_residue_arithmetic() {
+    let env = Environment::new();
+    let eval = |expression| env.eval(&expression).unwrap();
+    assert_eq!(eval(residue(-1, 7)), Residue { value: 6, modulus: 7 });
+    assert_eq!(eval(infix(residue(3, 7), Token::Plus, IntegerLiteral(5))), Residue { value: 1, modulus: 7 });
+    assert_eq!(eval(infix(residue(3, 7), Token::Minus, residue(5, 7))), Residue { value: 5, modulus: 7 });
+    assert_eq!(eval(infix(residue(3, 7), Token::Multiply, residue(4, 7))), Residue { value: 5, modulus: 7 });
+    assert_eq!(eval(infix(residue(3, 7), Token::Division, residue(2, 7))), Residue { value: 5, modulus: 7 });
+    assert_eq!(mem::discriminant(&eval(infix(residue(3, 8), Token::Division, residue(2, 8)))), mem::discriminant(&Undefined));
+    assert!(env.eval(&infix(residue(3, 7), Token::Plus, residue(3, 5))).is_err());
+}
+
+#[test]
+fn test_residue_powers() {
+    let env = Environment::new();
+    let eval = |expression| env.eval(&expression).unwrap();
+    assert_eq!(eval(infix(residue(3, 7), Token::Exponent, IntegerLiteral(100))), Residue { value: 4, modulus: 7 });
+    assert_eq!(eval(infix(residue(3, 7), Token::Exponent, IntegerLiteral(-1))), Residue { value: 5, modulus: 7 });
+    // far too large to compute before reducing
+    assert_eq!(eval(infix(residue(2, 1_000_003), Token::Exponent, IntegerLiteral(i64::MAX))), Residue { value: 625903, modulus: 1_000_003 });
+    assert_eq!(run("(2 mod 7) ^ (10 ^ 30);").unwrap(), "2 mod 7");
+    assert_eq!(run("(2 mod 7) ^ -(10 ^ 30);").unwrap(), "4 mod 7");
+}
+
+#[test]
+fn test_mod_binds_like_multiplication() {
+    assert_eq!(run("2 + 3 mod 7;").unwrap(), "5 mod 7");
+    assert_eq!(run("3 mod 7 + 5;").unwrap(), "1 mod 7");
+    assert_eq!(run("3 mod 7 + 5 mod 7;").unwrap(), "1 mod 7");
+    assert_eq!(run("3 mod 7 - 5 mod 7;").unwrap(), "5 mod 7");
+    assert_eq!(run("3 mod 7 * 2;").unwrap(), "6 mod 7");
+    assert_eq!(run("2 * 5 mod 7;").unwrap(), "3 mod 7");
+    assert_eq!(run("2 ^ 5 mod 7;").unwrap(), "4 mod 7");
+}
+
+#[test]
+fn test_mod_as_a_name() {
+    assert_eq!(run("let mod = 3; mod + 1;").unwrap(), "4");
+    assert_eq!(run("fn f(mod) = mod * 2; f(4);").unwrap(), "8");
+    assert_eq!(run("let mod = 3; 10 mod mod;").unwrap(), "1 mod 3");
+}
+
+#[test]
+fn test_congruence() {
+    let env = Environment::new();
+    assert_eq!(env.eval(&infix(IntegerLiteral(38), Token::Congruent, residue(3, 7))).unwrap(), Boolean(true));
+    assert_eq!(env.eval(&infix(IntegerLiteral(38), Token::Congruent, residue(4, 7))).unwrap(), Boolean(false));
+    assert!(env.eval(&infix(IntegerLiteral(38), Token::Congruent, IntegerLiteral(3))).is_err());
+}
+
+#[test]
+fn test_residue_builtins() {
+    assert_eq!(call("inverse", vec![residue(3, 7)]), Residue { value: 5, modulus: 7 });
+    assert_eq!(mem::discriminant(&call("inverse", vec![residue(2, 8)])), mem::discriminant(&Undefined));
+    assert_eq!(call("crt", vec![residue(2, 3), residue(3, 5), residue(2, 7)]), Residue { value: 23, modulus: 105 });
+    // the moduli need not be coprime as long as the congruences agree
+    assert_eq!(call("crt", vec![residue(1, 4), residue(3, 6)]), Residue { value: 9, modulus: 12 });
+    assert_eq!(mem::discriminant(&call("crt", vec![residue(1, 4), residue(2, 6)])), mem::discriminant(&Undefined));
+    assert_eq!(call("dlog", vec![residue(3, 7), IntegerLiteral(6)]), Integer(3));
+    assert_eq!(call("dlog", vec![residue(2, 1_000_003), IntegerLiteral(12345)]), Integer(573284));
+    assert_eq!(call("dlog", vec![residue(2, 12), IntegerLiteral(8)]), Integer(3));
+    assert_eq!(mem::discriminant(&call("dlog", vec![residue(2, 7), IntegerLiteral(3)])), mem::discriminant(&Undefined));
+}
+
+fn lambda(parameter: &str, body: crate::ast::expression::Expression) -> crate::ast::expression::Expression {
+    FunctionLiteral {
+        parameters: vec![crate::ast::parameter::Parameter { name: parameter.into(), domain: None }],
+        body: Box::new(body),
+    }
+}
+
+#[test]
+fn test_function_display_inside_containers() {
+    assert_eq!(run("{ x => x ^ 2, x => x + 1 };").unwrap(), "{ fn(x) = x ^ 2, fn(x) = x + 1 }");
+    assert_eq!(run("fn f(x) = x ^ 2; (f, 1);").unwrap(), "(fn(x) = x ^ 2, 1)");
+    assert_eq!(run("fn f(x: N) = 0; fn f(x: Z) = 1; f;").unwrap(), "fn(x: N) = 0; fn(x: Z) = 1");
+}
+
+#[test]
+fn test_function_literal_call() {
+    let obj = Environment::new().eval(&Call {
+        function: Box::new(lambda("x", infix(Identifier("x".into()), Token::Exponent, IntegerLiteral(2)))),
+        arguments: vec![IntegerLiteral(7)],
+    }).unwrap();
+    assert_eq!(obj, Integer(49));
+}
+
+#[test]
+fn test_set_of_function_literals() {
+    let square = || lambda("x", infix(Identifier("x".into()), Token::Exponent, IntegerLiteral(2)));
+    let successor = lambda("x", infix(Identifier("x".into()), Token::Plus, IntegerLiteral(1)));
+    match Environment::new().eval(&SetLiteral(vec![square(), successor, square()])).unwrap() {
+        Set { items, .. } => assert_eq!(items.len(), 2),
+        obj => panic!("Expected a set of functions got {}.", obj),
+    }
+}
+
+fn declare(env: &mut Environment, identifier: &str, parameter: &str, expression: crate::ast::expression::Expression) {
+    env.eval_statement(&Statement::FunctionDeclaration {
+        identifier: identifier.into(),
+        parameters: vec![crate::ast::parameter::Parameter { name: parameter.into(), domain: None }],
+        codomain: None,
+        expression,
+    }).unwrap();
+}
+
+fn call_with(env: &Environment, function: crate::ast::expression::Expression, argument: i64) -> Result<crate::ast::object::Object, crate::ast::errors::RuntimeError> {
+    env.eval(&Call { function: Box::new(function), arguments: vec![IntegerLiteral(argument)] })
+}
+
+#[test]
+fn test_closure_captures_parameters() {
+    // fn adder(a) = x => x + a;
+    let mut env = Environment::new();
+    declare(&mut env, "adder", "a", lambda("x", infix(Identifier("x".into()), Token::Plus, Identifier("a".into()))));
+    let add2 = Call { function: Box::new(Identifier("adder".into())), arguments: vec![IntegerLiteral(2)] };
+    assert_eq!(call_with(&env, add2.clone(), 10).unwrap(), Integer(12));
+    // closures over different values are different functions
+    let add3 = Call { function: Box::new(Identifier("adder".into())), arguments: vec![IntegerLiteral(3)] };
+    match env.eval(&SetLiteral(vec![add2, add3])).unwrap() {
+        Set { items, .. } => assert_eq!(items.len(), 2),
+        obj => panic!("Expected a set of functions got {}.", obj),
+    }
+}
+
+#[test]
+fn test_free_names_are_lexical() {
+    // fn get_y(t) = y; fn shadow(y) = get_y(0);
+    let mut env = Environment::new();
+    declare(&mut env, "get_y", "t", Identifier("y".into()));
+    declare(&mut env, "shadow", "y", Call { function: Box::new(Identifier("get_y".into())), arguments: vec![IntegerLiteral(0)] });
+    assert!(call_with(&env, Identifier("shadow".into()), 5).is_err());
+    // a parameter may hide a builtin
+    declare(&mut env, "next", "degree", infix(Identifier("degree".into()), Token::Plus, IntegerLiteral(1)));
+    assert_eq!(call_with(&env, Identifier("next".into()), 2).unwrap(), Integer(3));
+}
+
+#[test]
+fn test_argument_outside_domain() {
+    // fn pred(n in N) -> N = n - 1;
+    let mut env = Environment::new();
+    env.eval_statement(&Statement::FunctionDeclaration {
+        identifier: "pred".into(),
+        parameters: vec![crate::ast::parameter::Parameter { name: "n".into(), domain: Some(Identifier("N".into())) }],
+        codomain: Some(Identifier("N".into())),
+        expression: infix(Identifier("n".into()), Token::Minus, IntegerLiteral(1)),
+    }).unwrap();
+    assert_eq!(call_with(&env, Identifier("pred".into()), 5).unwrap(), Integer(4));
+    assert_eq!(mem::discriminant(&call_with(&env, Identifier("pred".into()), -5).unwrap()), mem::discriminant(&Undefined));
+    // 0 is natural but -1 is not
+    assert!(call_with(&env, Identifier("pred".into()), 0).is_err());
+}
+
+fn declare_on(env: &mut Environment, identifier: &str, domain: crate::ast::expression::Expression, value: i64) -> Result<crate::ast::object::Object, crate::ast::errors::RuntimeError> {
+    env.eval_statement(&Statement::FunctionDeclaration {
+        identifier: identifier.into(),
+        parameters: vec![crate::ast::parameter::Parameter { name: "x".into(), domain: Some(domain) }],
+        codomain: None,
+        expression: IntegerLiteral(value),
+    })
+}
+
+#[test]
+fn test_overloads_dispatch_to_most_specific() {
+    let mut env = Environment::new();
+    declare_on(&mut env, "f", Identifier("R".into()), 1).unwrap();
+    declare_on(&mut env, "f", Identifier("N".into()), 2).unwrap();
+    declare_on(&mut env, "f", Identifier("Z".into()), 3).unwrap();
+    assert_eq!(call_with(&env, Identifier("f".into()), 4).unwrap(), Integer(2));
+    assert_eq!(call_with(&env, Identifier("f".into()), -4).unwrap(), Integer(3));
+    assert_eq!(env.eval(&Call { function: Box::new(Identifier("f".into())), arguments: vec![RealLiteral("0.5".into())] }).unwrap(), Integer(1));
+    assert_eq!(mem::discriminant(&env.eval(&Call { function: Box::new(Identifier("f".into())), arguments: vec![BooleanLiteral(true)] }).unwrap()), mem::discriminant(&Undefined));
+    // the same domains again
+    assert!(declare_on(&mut env, "f", Identifier("Z".into()), 4).is_err());
+}
+
+#[test]
+fn test_ambiguous_overloads() {
+    let mut env = Environment::new();
+    declare_on(&mut env, "f", SetLiteral(vec![IntegerLiteral(1), IntegerLiteral(2)]), 1).unwrap();
+    declare_on(&mut env, "f", SetLiteral(vec![IntegerLiteral(2), IntegerLiteral(3)]), 2).unwrap();
+    assert_eq!(call_with(&env, Identifier("f".into()), 1).unwrap(), Integer(1));
+    assert!(call_with(&env, Identifier("f".into()), 2).is_err());
+    assert_eq!(
+        run("fn f(x in Z, y) = 1; fn f(x, y in Z) = 2; f(1, 2);").unwrap_err(),
+        "TypeError: Ambiguous call with arguments (1, 2): none of fn(x: Z, y) = 1; fn(x, y: Z) = 2 is more specific than the others."
+    );
+}
+
+#[test]
+fn test_only_declared_functions_are_overloaded() {
+    assert_eq!(run("let g = x => x; fn g(x: N) = 0; g(3);").unwrap_err(), "NameError: Cannot re-declare value `g`.");
+    assert_eq!(run("fn g(x: N) = 0; let g = x => x; g(3);").unwrap_err(), "NameError: Cannot re-declare value `g`.");
+    assert_eq!(run("fn g(x: N) = 0; fn g(x: Z) = 1; g(-3);").unwrap(), "1");
+}
+
+// square is x => x ^ 2 and successor is x => x + 1
+fn with_square_and_successor() -> Environment {
+    let mut env = Environment::new();
+    for (name, body) in [
+        ("square", infix(Identifier("x".into()), Token::Exponent, IntegerLiteral(2))),
+        ("successor", infix(Identifier("x".into()), Token::Plus, IntegerLiteral(1))),
+    ] {
+        env.eval_statement(&Statement::Assignment { identifier: name.into(), expression: lambda("x", body) }).unwrap();
+    }
+    env
+}
+
+#[test]
+fn test_pointwise_function_arithmetic() {
+    let env = with_square_and_successor();
+    let (square, successor) = (|| Identifier("square".into()), || Identifier("successor".into()));
+    assert_eq!(call_with(&env, infix(square(), Token::Plus, successor()), 3).unwrap(), Integer(13));
+    assert_eq!(call_with(&env, infix(IntegerLiteral(2), Token::Multiply, square()), 3).unwrap(), Integer(18));
+    assert_eq!(call_with(&env, infix(square(), Token::Division, successor()), 3).unwrap(), Rational { numerator: 9, denominator: 4 });
+    assert_eq!(env.eval(&infix(square(), Token::Plus, successor())).unwrap().to_string(), "(x => x ^ 2) + (x => x + 1)");
+    assert!(env.eval(&infix(square(), Token::Plus, SetLiteral(vec![]))).is_err());
+}
+
+#[test]
+fn test_function_arithmetic_checks_arity() {
+    assert!(run("fn f(x) = x ^ 2; let g = (a, b) => a; f + g;").unwrap_err().starts_with("TypeError"));
+    assert!(run("let h = (a, b) => a + b; h ∘ (x => x);").unwrap_err().starts_with("TypeError"));
+    assert!(run("let h = (a, b) => a + b; h ^ 2;").unwrap_err().starts_with("TypeError"));
+    assert_eq!(run("let h = (a, b) => a + b; (2 * h)(1, 2);").unwrap(), "6");
+}
+
+#[test]
+fn test_function_composition() {
+    let env = with_square_and_successor();
+    let (square, successor) = (|| Identifier("square".into()), || Identifier("successor".into()));
+    assert_eq!(call_with(&env, infix(square(), Token::Compose, successor()), 3).unwrap(), Integer(16));
+    assert_eq!(call_with(&env, infix(successor(), Token::Compose, square()), 3).unwrap(), Integer(10));
+    assert_eq!(env.eval(&infix(square(), Token::Compose, successor())).unwrap().to_string(), "(x => x ^ 2) ∘ (x => x + 1)");
+    assert!(env.eval(&infix(square(), Token::Compose, IntegerLiteral(2))).is_err());
+}
+
+#[test]
+fn test_iterated_function() {
+    let env = with_square_and_successor();
+    let successor = || Identifier("successor".into());
+    assert_eq!(call_with(&env, infix(successor(), Token::Exponent, IntegerLiteral(5)), 0).unwrap(), Integer(5));
+    assert_eq!(call_with(&env, infix(successor(), Token::Exponent, IntegerLiteral(0)), 7).unwrap(), Integer(7));
+    assert_eq!(call_with(&env, infix(successor(), Token::Exponent, IntegerLiteral(2)), 3).unwrap(), Integer(5));
+    assert_eq!(env.eval(&infix(successor(), Token::Exponent, IntegerLiteral(5))).unwrap().to_string(), "(x => x + 1) ^ 5");
+    // a number raised to a function is pointwise
+    assert_eq!(call_with(&env, infix(IntegerLiteral(2), Token::Exponent, successor()), 3).unwrap(), Integer(16));
+    assert!(env.eval(&infix(successor(), Token::Exponent, IntegerLiteral(-1))).is_err());
+}
@@ -0,0 +1,165 @@
+use super::object::{NumberSet, Object};
+use super::units::{self, Dimension};
+use std::fmt::{Display, Formatter, self};
+
+// the structural type of a value; the members of a set must all have types that join
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    Unknown,  // the members of an empty collection, which join with anything
+    Integer,
+    Rational,
+    Real,
+    Complex,
+    Boolean,
+    Atom,
+    Undefined,
+    Function(usize),  // the number of parameters
+    Overloads,  // functions whose definitions take different numbers of parameters
+    Set(Box<Type>),
+    Symbolic(Box<Type>),  // sets described rather than listed, like N or [0, 1), which are only compared with each other
+    Multiset(Box<Type>),
+    Tuple(Vec<Type>),
+    Vector(Box<Type>),  // the type of the entries
+    Matrix(Box<Type>),
+    Mixed,  // the entries of a vector or matrix whose types don't join, which join with nothing
+    Graph,
+    Polynomial,
+    Quantity(Dimension),  // quantities can only be compared when they measure the same thing
+    Enclosure,
+    Residue(i64),  // the modulus
+}
+
+impl Type {
+    pub fn of(object: &Object) -> Self {
+        match object {
+            Object::Integer(_) | Object::BigInteger(_) => Type::Integer,
+            Object::Rational { .. } | Object::BigRational { .. } => Type::Rational,
+            Object::Real(_) | Object::Decimal(_) => Type::Real,
+            Object::Complex { .. } => Type::Complex,
+            Object::Boolean(_) => Type::Boolean,
+            Object::Atom { .. } => Type::Atom,
+            Object::Undefined => Type::Undefined,
+            Object::Function { parameters, .. } | Object::BuiltinFunction { parameters, .. } => Type::Function(parameters.len()),
+            // overloads taking different numbers of parameters are only comparable with each other
+            Object::Composite(composite) => composite.kind(),
+            Object::Overloads(definitions) => match Type::of_members(definitions) {
+                Some(kind) => kind,
+                None => Type::Overloads,
+            },
+            Object::Set { kind, .. } => Type::Set(Box::new(kind.clone())),
+            Object::Multiset { kind, .. } => Type::Multiset(Box::new(kind.clone())),
+            Object::SetBuilder { parent_set: universe, .. } | Object::Complement { universe, .. } => {
+                Type::Symbolic(Box::new(Type::of(universe).members()))
+            },
+            Object::Range { .. } => Type::Set(Box::new(Type::Integer)),
+            Object::NumberSet(set) => Type::Symbolic(Box::new(match set {
+                NumberSet::Naturals | NumberSet::Integers => Type::Integer,
+                NumberSet::Rationals => Type::Rational,
+                NumberSet::Reals => Type::Real,
+                NumberSet::Complexes => Type::Complex,
+            })),
+            Object::Intervals(_) => Type::Symbolic(Box::new(Type::Real)),
+            Object::Tuple { items } => Type::Tuple(items.iter().map(Type::of).collect()),
+            Object::Vector { items } => Type::Vector(Box::new(Type::of_members(items).unwrap_or(Type::Mixed))),
+            Object::Matrix { rows } => Type::Matrix(Box::new(Type::of_members(rows.iter().flatten()).unwrap_or(Type::Mixed))),
+            Object::Graph { .. } => Type::Graph,
+            Object::Polynomial(_) => Type::Polynomial,
+            Object::Quantity { unit, .. } => Type::Quantity(unit.dimension),
+            Object::Enclosure(_) => Type::Enclosure,
+            Object::Residue { modulus, .. } => Type::Residue(*modulus),
+        }
+    }
+
+    // the type shared by all of the members, or None when two of them cannot be compared
+    pub fn of_members<'a>(members: impl IntoIterator<Item=&'a Object>) -> Option<Self> {
+        members.into_iter().try_fold(Type::Unknown, |kind, member| kind.join(&Type::of(member)))
+    }
+
+    // the type of the members of a set
+    fn members(&self) -> Self {
+        match self {
+            Type::Set(kind) | Type::Symbolic(kind) => *kind.clone(),
+            _ => Type::Unknown,
+        }
+    }
+
+    // integers are rationals, and rationals are reals
+    fn numeric_rank(&self) -> Option<u8> {
+        match self {
+            Type::Integer => Some(0),
+            Type::Rational => Some(1),
+            Type::Real => Some(2),
+            _ => None,
+        }
+    }
+
+    // the narrowest type covering both, or None when values of the two types cannot be compared
+    pub fn join(&self, other: &Self) -> Option<Self> {
+        use Type::*;
+        match (self, other) {
+            (Unknown, known) | (known, Unknown) => Some(known.clone()),
+            (Mixed, _) | (_, Mixed) => None,
+            // before equal types join, as entries of mixed types don't
+            (Vector(left), Vector(right)) => Some(Vector(Box::new(left.join(right)?))),
+            (Matrix(left), Matrix(right)) => Some(Matrix(Box::new(left.join(right)?))),
+            (left, right) if left == right => Some(left.clone()),
+            (left, right) if left.numeric_rank().is_some() && right.numeric_rank().is_some() => {
+                Some(if left.numeric_rank() > right.numeric_rank() { left.clone() } else { right.clone() })
+            },
+            (Set(left), Set(right)) => Some(Set(Box::new(left.join(right)?))),
+            (Symbolic(left), Symbolic(right)) => Some(Symbolic(Box::new(left.join(right)?))),
+            (Multiset(left), Multiset(right)) => Some(Multiset(Box::new(left.join(right)?))),
+            // tuples are compared position by position, so only the positions both have need to join
+            (Tuple(left), Tuple(right)) => {
+                let (shorter, longer) = if left.len() <= right.len() { (left, right) } else { (right, left) };
+                let mut items = shorter.iter().zip(longer).map(|(left, right)| left.join(right)).collect::<Option<Vec<_>>>()?;
+                items.extend(longer[shorter.len()..].iter().cloned());
+                Some(Tuple(items))
+            },
+            _ => None,
+        }
+    }
+
+    // whether values of this type can be put in order with `<`
+    pub fn ordered(&self) -> bool {
+        use Type::*;
+        match self {
+            Tuple(items) => items.iter().all(Type::ordered),
+            Vector(kind) | Matrix(kind) => kind.ordered(),
+            // sets are only ordered by inclusion, which doesn't carry over to what holds them
+            Set(_) | Symbolic(_) | Multiset(_) => false,
+            Mixed | Complex | Graph | Polynomial => false,
+            _ => true,
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use Type::*;
+        match self {
+            Unknown => write!(f, "Unknown"),
+            Integer => write!(f, "Integer"),
+            Rational => write!(f, "Rational"),
+            Real => write!(f, "Real"),
+            Complex => write!(f, "Complex"),
+            Boolean => write!(f, "Boolean"),
+            Atom => write!(f, "Atom"),
+            Undefined => write!(f, "Undefined"),
+            Function(arity) => write!(f, "Function({})", arity),
+            Overloads => write!(f, "Overloads"),
+            Set(kind) => write!(f, "Set<{}>", kind),
+            Symbolic(kind) => write!(f, "SymbolicSet<{}>", kind),
+            Multiset(kind) => write!(f, "Multiset<{}>", kind),
+            Tuple(items) => write!(f, "({})", items.iter().map(Type::to_string).collect::<Vec<_>>().join(", ")),
+            Vector(kind) => write!(f, "Vector<{}>", kind),
+            Matrix(kind) => write!(f, "Matrix<{}>", kind),
+            Mixed => write!(f, "Mixed"),
+            Graph => write!(f, "Graph"),
+            Polynomial => write!(f, "Polynomial"),
+            Quantity(dimension) => write!(f, "Quantity({})", units::dimension_name(dimension)),
+            Enclosure => write!(f, "Enclosure"),
+            Residue(modulus) => write!(f, "Residue({})", modulus),
+        }
+    }
+}
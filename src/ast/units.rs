@@ -0,0 +1,235 @@
+use super::errors::RuntimeError;
+use super::object::Object;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, self};
+
+// powers of the metre, kilogram, second, ampere, kelvin, mole and candela
+pub type Dimension = [i32; 7];
+
+const BASE_SYMBOLS: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+const DIMENSIONLESS: Dimension = [0; 7];
+
+// a product of powers of named units, such as m/s^2
+#[derive(Debug, Clone)]
+pub struct Unit {
+    pub symbols: BTreeMap<String, i32>,  // each named unit with its (never zero) power
+    pub scale: Box<Object>,  // the size of the unit in base SI units, e.g. 1000 for km
+    pub dimension: Dimension,
+}
+
+impl Unit {
+    fn named(symbol: &str, scale: Object, dimension: Dimension) -> Self {
+        let mut symbols = BTreeMap::new();
+        symbols.insert(symbol.to_string(), 1);
+        Unit { symbols, scale: Box::new(scale), dimension }
+    }
+
+    // the unit of plain numbers
+    fn one() -> Self {
+        Unit { symbols: BTreeMap::new(), scale: Box::new(Object::Integer(1)), dimension: DIMENSIONLESS }
+    }
+
+    // this unit multiplied by another raised to a power
+    fn combine(&self, other: &Unit, power: i32) -> Result<Self, RuntimeError> {
+        let mut symbols = self.symbols.clone();
+        for (symbol, exponent) in &other.symbols {
+            let total = symbols.get(symbol).copied().unwrap_or(0) + exponent * power;
+            if total == 0 {
+                symbols.remove(symbol);
+            } else {
+                symbols.insert(symbol.clone(), total);
+            }
+        }
+        let mut dimension = self.dimension;
+        for (base, exponent) in dimension.iter_mut().zip(other.dimension.iter()) {
+            *base += exponent * power;
+        }
+        Ok(Unit {
+            symbols,
+            scale: Box::new((*self.scale.clone() * other.scale.pow(&Object::Integer(power.into()))?)?),
+            dimension,
+        })
+    }
+}
+
+// powers written as a*b^2/(c*d), or as c^-1*d^-1 when none are positive
+fn format_powers<'a>(powers: impl Iterator<Item=(&'a str, i32)>) -> String {
+    let powers = powers.collect::<Vec<_>>();
+    let power = |symbol: &str, power: i32| if power == 1 { symbol.to_string() } else { format!("{}^{}", symbol, power) };
+    if powers.iter().all(|(_, power)| *power < 0) {
+        return powers.iter().map(|(symbol, exponent)| power(symbol, *exponent)).collect::<Vec<_>>().join("*")
+    };
+    let numerator = powers.iter().filter(|(_, exponent)| *exponent > 0).map(|(symbol, exponent)| power(symbol, *exponent)).collect::<Vec<_>>();
+    let denominator = powers.iter().filter(|(_, exponent)| *exponent < 0).map(|(symbol, exponent)| power(symbol, -exponent)).collect::<Vec<_>>();
+    match denominator.len() {
+        0 => numerator.join("*"),
+        1 => format!("{}/{}", numerator.join("*"), denominator[0]),
+        _ => format!("{}/({})", numerator.join("*"), denominator.join("*")),
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_powers(self.symbols.iter().map(|(symbol, power)| (symbol.as_str(), *power))))
+    }
+}
+
+// a dimension in terms of the base units
+pub fn dimension_name(dimension: &Dimension) -> String {
+    format_powers(BASE_SYMBOLS.iter().zip(dimension.iter()).filter(|(_, power)| **power != 0).map(|(symbol, power)| (*symbol, *power)))
+}
+
+// a number with a unit; units without a dimension, such as m/km, just scale the number
+pub fn quantity(magnitude: Object, unit: Unit) -> Result<Object, RuntimeError> {
+    if unit.dimension == DIMENSIONLESS {
+        magnitude * *unit.scale
+    } else {
+        Ok(Object::Quantity { magnitude: Box::new(magnitude), unit })
+    }
+}
+
+// plain numbers are quantities without a unit
+fn parts(value: &Object) -> Option<(Object, Unit)> {
+    match value {
+        Object::Quantity { magnitude, unit } => Some((*magnitude.clone(), unit.clone())),
+        number if number.is_number() => Some((number.clone(), Unit::one())),
+        _ => None,
+    }
+}
+
+// whether both sides can take part in unit arithmetic, with at least one of them a quantity
+pub fn operands(left: &Object, right: &Object) -> bool {
+    (matches!(left, Object::Quantity { .. }) || matches!(right, Object::Quantity { .. })) && parts(left).is_some() && parts(right).is_some()
+}
+
+// the value in base SI units, for comparing quantities
+pub fn base_value(magnitude: &Object, unit: &Unit) -> Object {
+    (magnitude.clone() * *unit.scale.clone()).unwrap_or(Object::Undefined)
+}
+
+// the right side is converted to the unit of the left side
+pub fn add(left: &Object, right: &Object, subtract: bool) -> Result<Object, RuntimeError> {
+    let ((magnitude, unit), (magnitude2, unit2)) = (parts(left).unwrap(), parts(right).unwrap());
+    if unit.dimension != unit2.dimension {
+        let operation = if subtract { "subtract" } else { "add" };
+        return Err(RuntimeError::OperatorError(format!("Cannot {} {} and {} (their dimensions differ).", operation, left, right)))
+    };
+    let converted = ((magnitude2 * *unit2.scale)? / *unit.scale.clone())?;
+    quantity(if subtract { (magnitude - converted)? } else { (magnitude + converted)? }, unit)
+}
+
+// quantities only compare with others of the same dimension, where plain numbers have none
+pub fn comparable(left: &Object, right: &Object) -> Result<(), RuntimeError> {
+    let (unit, unit2) = (parts(left).unwrap().1, parts(right).unwrap().1);
+    if unit.dimension == unit2.dimension {
+        Ok(())
+    } else {
+        Err(RuntimeError::OperatorError(format!("Cannot compare {} and {} (their dimensions differ).", left, right)))
+    }
+}
+
+// a product when power is 1 and a quotient when it is -1
+pub fn multiply(left: &Object, right: &Object, power: i32) -> Result<Object, RuntimeError> {
+    let ((magnitude, unit), (magnitude2, unit2)) = (parts(left).unwrap(), parts(right).unwrap());
+    let magnitude = if power < 0 { (magnitude / magnitude2)? } else { (magnitude * magnitude2)? };
+    if let Object::Undefined = magnitude {
+        return Ok(Object::Undefined)
+    };
+    quantity(magnitude, unit.combine(&unit2, power)?)
+}
+
+pub fn pow(value: &Object, exponent: &Object) -> Result<Object, RuntimeError> {
+    match (value, exponent) {
+        (Object::Quantity { magnitude, unit }, Object::Integer(power)) if i32::try_from(*power).is_ok() => {
+            quantity(magnitude.pow(exponent)?, Unit::one().combine(unit, *power as i32)?)
+        },
+        _ => Err(RuntimeError::OperatorError(format!("Cannot raise {} to {} (expected an integer power).", value, exponent))),
+    }
+}
+
+// `x as km`: the same quantity expressed in the unit of the target
+pub fn convert(value: &Object, target: &Object) -> Result<Object, RuntimeError> {
+    match (parts(value), parts(target)) {
+        (Some((magnitude, unit)), Some((_, target_unit))) if unit.dimension == target_unit.dimension => {
+            quantity(((magnitude * *unit.scale)? / *target_unit.scale.clone())?, target_unit)
+        },
+        _ => Err(RuntimeError::OperatorError(format!("Cannot convert {} to the unit of {}.", value, target))),
+    }
+}
+
+fn exact(numerator: i64, denominator: i64) -> Object {
+    Object::rational(numerator.into(), denominator.into())
+}
+
+// the units named by the units module, with whether they take prefixes
+fn named_units() -> Vec<(&'static str, Object, Dimension, bool)> {
+    vec![
+        ("m", exact(1, 1), [1, 0, 0, 0, 0, 0, 0], true),
+        ("g", exact(1, 1000), [0, 1, 0, 0, 0, 0, 0], true),
+        ("s", exact(1, 1), [0, 0, 1, 0, 0, 0, 0], true),
+        ("A", exact(1, 1), [0, 0, 0, 1, 0, 0, 0], true),
+        ("K", exact(1, 1), [0, 0, 0, 0, 1, 0, 0], true),
+        ("mol", exact(1, 1), [0, 0, 0, 0, 0, 1, 0], true),
+        ("cd", exact(1, 1), [0, 0, 0, 0, 0, 0, 1], true),
+        ("Hz", exact(1, 1), [0, 0, -1, 0, 0, 0, 0], true),
+        ("N", exact(1, 1), [1, 1, -2, 0, 0, 0, 0], true),
+        ("Pa", exact(1, 1), [-1, 1, -2, 0, 0, 0, 0], true),
+        ("J", exact(1, 1), [2, 1, -2, 0, 0, 0, 0], true),
+        ("W", exact(1, 1), [2, 1, -3, 0, 0, 0, 0], true),
+        ("C", exact(1, 1), [0, 0, 1, 1, 0, 0, 0], true),
+        ("V", exact(1, 1), [2, 1, -3, -1, 0, 0, 0], true),
+        ("F", exact(1, 1), [-2, -1, 4, 2, 0, 0, 0], true),
+        ("ohm", exact(1, 1), [2, 1, -3, -2, 0, 0, 0], true),
+        ("S", exact(1, 1), [-2, -1, 3, 2, 0, 0, 0], true),
+        ("Wb", exact(1, 1), [2, 1, -2, -1, 0, 0, 0], true),
+        ("T", exact(1, 1), [0, 1, -2, -1, 0, 0, 0], true),
+        ("H", exact(1, 1), [2, 1, -2, -2, 0, 0, 0], true),
+        ("L", exact(1, 1000), [3, 0, 0, 0, 0, 0, 0], true),
+        ("eV", Object::Real(1.602176634e-19), [2, 1, -2, 0, 0, 0, 0], true),
+        ("minute", exact(60, 1), [0, 0, 1, 0, 0, 0, 0], false),
+        ("h", exact(3600, 1), [0, 0, 1, 0, 0, 0, 0], false),
+        ("day", exact(86400, 1), [0, 0, 1, 0, 0, 0, 0], false),
+    ]
+}
+
+const PREFIXES: [(&str, i64, i64); 7] = [
+    ("G", 1_000_000_000, 1),
+    ("M", 1_000_000, 1),
+    ("k", 1000, 1),
+    ("c", 1, 100),
+    ("m", 1, 1000),
+    ("u", 1, 1_000_000),
+    ("n", 1, 1_000_000_000),
+];
+
+// every unit with and without its prefixes, each as a quantity of one
+pub fn standard_units() -> Result<Vec<(String, Object)>, RuntimeError> {
+    let mut units = vec![];
+    for (symbol, scale, dimension, prefixed) in named_units() {
+        if prefixed {
+            for (prefix, numerator, denominator) in PREFIXES.iter() {
+                let symbol = format!("{}{}", prefix, symbol);
+                let scale = (scale.clone() * exact(*numerator, *denominator))?;
+                units.push((symbol.clone(), quantity(Object::Integer(1), Unit::named(&symbol, scale, dimension))?));
+            }
+        };
+        units.push((symbol.to_string(), quantity(Object::Integer(1), Unit::named(symbol, scale, dimension))?));
+    }
+    Ok(units)
+}
+
+// physical constants in terms of the standard units
+pub fn constants(units: &BTreeMap<String, Object>) -> Result<Vec<(String, Object)>, RuntimeError> {
+    let unit = |symbol: &str| units[symbol].clone();
+    Ok(vec![
+        ("c".to_string(), multiply(&(Object::Integer(299_792_458) * unit("m"))?, &unit("s"), -1)?),
+        ("G".to_string(), (Object::Real(6.67430e-11) * multiply(&unit("m").pow(&Object::Integer(3))?, &(unit("kg") * unit("s").pow(&Object::Integer(2))?)?, -1)?)?),
+        ("g0".to_string(), multiply(&(Object::Real(9.80665) * unit("m"))?, &unit("s").pow(&Object::Integer(2))?, -1)?),
+        ("planck".to_string(), (Object::Real(6.62607015e-34) * (unit("J") * unit("s"))?)?),
+        ("boltzmann".to_string(), multiply(&(Object::Real(1.380649e-23) * unit("J"))?, &unit("K"), -1)?),
+        ("avogadro".to_string(), multiply(&Object::Real(6.02214076e23), &unit("mol"), -1)?),
+        ("elementary_charge".to_string(), (Object::Real(1.602176634e-19) * unit("C"))?),
+    ])
+}
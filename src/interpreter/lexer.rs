@@ -1,5 +1,6 @@
 use super::token::Token;
 use itertools::Itertools;
+use num_bigint::BigInt;
 use std::iter::Peekable;
 use std::str::Chars;
 
@@ -23,6 +24,8 @@ impl<'a> IntoIterator for Lexer<'a> {
         LexerIterator {
             iterator: self.input.chars().peekable(),
             done: false,
+            pending: None,
+            previous: None,
         }
     }
 }
@@ -31,6 +34,8 @@ impl<'a> IntoIterator for Lexer<'a> {
 pub struct LexerIterator<'b> {
     iterator: Peekable<Chars<'b>>,
     done: bool,
+    pending: Option<Token>,  // a token that was lexed alongside the previous one (e.g. the `i` in `3i`)
+    previous: Option<Token>,
 }
 
 impl<'b> LexerIterator<'b> {
@@ -38,16 +43,30 @@ impl<'b> LexerIterator<'b> {
         Self {
             iterator,
             done: false,
+            pending: None,
+            previous: None,
         }
     }
 }
 
-impl Iterator for LexerIterator<'_> {
-    type Item = Token;
+impl LexerIterator<'_> {
+    // `mod` is only the operator straight after an operand, so it can still name things elsewhere
+    fn follows_operand(&self) -> bool {
+        use Token::*;
+        matches!(
+            self.previous,
+            Some(Identifier(_) | Integer(_) | BigInteger(_) | Real(_) | True | False | Undefined
+                | RightParen | RightSquareBracket | RightBrace | RightMultisetBrace | Bar | Bang | Imaginary)
+        )
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn lex(&mut self) -> Option<Token> {
         use Token::*;
 
+        if let Some(token) = self.pending.take() {
+            return Some(token);
+        };
+
         let mut in_comment = false;
 
         loop {
@@ -61,7 +80,11 @@ impl Iterator for LexerIterator<'_> {
                 let next_token = match (ch, next_ch) {
                     ('=', Some('=')) => {
                         self.iterator.next();
-                        Equals
+                        if self.iterator.next_if_eq(&'=').is_some() {
+                            Congruent
+                        } else {
+                            Equals
+                        }
                     }
                     ('=', Some('/')) => {
                         self.iterator.next();
@@ -79,6 +102,11 @@ impl Iterator for LexerIterator<'_> {
                         self.iterator.next();
                         SkinnyArrow
                     }
+                    // `f << g` spells `f ∘ g`, as in F#
+                    ('<', Some('<')) => {
+                        self.iterator.next();
+                        Compose
+                    }
                     ('<', Some('=')) => {
                         self.iterator.next();
                         LessThanEquals
@@ -108,6 +136,14 @@ impl Iterator for LexerIterator<'_> {
                         self.iterator.next();
                         Union
                     }
+                    ('{', Some(':')) => {
+                        self.iterator.next();
+                        LeftMultisetBrace
+                    }
+                    (':', Some('}')) => {
+                        self.iterator.next();
+                        RightMultisetBrace
+                    }
                     ('/', Some('\\')) => {
                         self.iterator.next();
                         Intersection
@@ -120,6 +156,14 @@ impl Iterator for LexerIterator<'_> {
                             None => Illegal("/_".into()),
                         }
                     }
+                    ('.', Some('.')) => {
+                        self.iterator.next();
+                        match self.iterator.next_if_eq(&'.') {
+                            Some(_) => Ellipsis,
+                            None => DoubleDot,
+                        }
+                    }
+                    ('\u{2218}', _) => Compose,
                     ('?', _) => Question,
                     ('<', _) => LessThan,
                     ('>', _) => GreaterThan,
@@ -131,7 +175,18 @@ impl Iterator for LexerIterator<'_> {
                     ('^', _) => Exponent,
                     ('{', _) => LeftBrace,
                     ('}', _) => RightBrace,
-                    ('(', _) => LeftParen,
+                    ('(', _) => {
+                        if self.follows_operand() && starts_mod_clause(&self.iterator) {
+                            self.iterator
+                                .take_while_ref(|ch| ch.is_whitespace())
+                                .for_each(drop);
+                            self.iterator.nth(2);
+                            ModClause
+                        } else {
+                            LeftParen
+                        }
+                    }
+                    ('\u{2261}', _) => Congruent,
                     (')', _) => RightParen,
                     ('[', _) => LeftSquareBracket,
                     (']', _) => RightSquareBracket,
@@ -144,7 +199,6 @@ impl Iterator for LexerIterator<'_> {
                     ('\\', _) => SetDifference,
                     ('#', _) => CommentStart,
                     ('\n', _) => NewLine,
-                    // ('i', _) => Imaginary,  // check for Identifier(...) | Real(...) | Integer(...) then Identifier("i")
                     (_, _) => {
                         if is_leading_identifier_char(&ch) {
                             let mut identifier = self
@@ -168,6 +222,7 @@ impl Iterator for LexerIterator<'_> {
                                 "not" => Not,
                                 "as" => As,
                                 "in" => In,
+                                "mod" if self.follows_operand() => Mod,
                                 "for" => For,
                                 "where" => Where,
                                 "import" => Import,
@@ -177,17 +232,32 @@ impl Iterator for LexerIterator<'_> {
                                 _ => Identifier(identifier),
                             }
                         } else if ch.is_numeric() || ch == '.' {
-                            let mut number_string = self
-                                .iterator
-                                .take_while_ref(|ch| ch.is_numeric() || *ch == '.')
-                                .collect::<String>();
+                            let mut number_string = ch.to_string();
+                            // a number stops before `..`, so `[0..1]` is an interval
+                            while let Some(&next) = self.iterator.peek() {
+                                let mut lookahead = self.iterator.clone();
+                                lookahead.next();
+                                if next.is_numeric() || (next == '.' && lookahead.peek() != Some(&'.')) {
+                                    number_string.push(next);
+                                    self.iterator.next();
+                                } else {
+                                    break
+                                }
+                            }
 
-                            number_string.insert(0, ch);
+                            // an `i` directly after a number (e.g. `3i`) marks it as imaginary
+                            let mut lookahead = self.iterator.clone();
+                            if !in_comment && lookahead.next() == Some('i') && !lookahead.peek().is_some_and(is_identifier) {
+                                self.iterator.next();
+                                self.pending = Some(Imaginary);
+                            };
 
                             if let Ok(number) = number_string.parse::<i64>() {
                                 Integer(number)
-                            } else if let Ok(number) = number_string.parse::<f64>() {
-                                Real(number)
+                            } else if let Ok(number) = number_string.parse::<BigInt>() {
+                                BigInteger(number)
+                            } else if number_string.parse::<f64>().is_ok() {
+                                Real(number_string)
                             } else {
                                 Illegal(ch.to_string())
                             }
@@ -227,6 +297,29 @@ impl Iterator for LexerIterator<'_> {
     }
 }
 
+impl Iterator for LexerIterator<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.lex();
+        self.previous = token.clone();
+        token
+    }
+}
+
+// whether `mod` and then a modulus come next, as in `(mod 7)` but not `f(mod)`
+fn starts_mod_clause(iterator: &Peekable<Chars>) -> bool {
+    let mut lookahead = iterator.clone().skip_while(|ch| ch.is_whitespace());
+    if !lookahead.by_ref().take(3).eq("mod".chars()) {
+        return false
+    };
+    match lookahead.next() {
+        Some(ch) if ch.is_whitespace() => lookahead.find(|ch| !ch.is_whitespace()).is_some_and(|ch| is_identifier(&ch) || ch == '('),
+        Some('(') => true,
+        _ => false,
+    }
+}
+
 // beef up the identifier tokens to include numbers in reasonable places
 fn is_identifier(ch: &char) -> bool {
     'a' <= *ch && *ch <= 'z' || 'A' <= *ch && *ch <= 'Z' || *ch == '_' || '0' <= *ch && *ch <= '9'
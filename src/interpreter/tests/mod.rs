@@ -36,7 +36,7 @@ lex!(
     vec![
         Identifier("pi".into()),
         Assign,
-        Real(3.14),
+        Real("3.14".into()),
         Identifier("radius".into()),
         Assign,
         Integer(10),
@@ -49,7 +49,7 @@ lex!(
         Integer(2),
         Identifier("area".into()),
         Equals,
-        Real(314.0),
+        Real("314.0".into()),
         Eof
     ]
 );
@@ -74,7 +74,7 @@ lex!(
         Colon,
         Illegal(".".into()),
         Comma,
-        Real(3.0),
+        Real("3.0".into()),
         Identifier("pi".into()),
         Modulo,
         Integer(2),
@@ -267,4 +267,154 @@ lex!(
         Identifier("B".into()),
         Eof
     ]
-);
\ No newline at end of file
+);
+lex!(
+    lex_imaginary_literals,
+    "3i + 2.5i - x",
+    vec![
+        Integer(3),
+        Imaginary,
+        Plus,
+        Real("2.5".into()),
+        Imaginary,
+        Minus,
+        Identifier("x".into()),
+        Eof
+    ]
+);
+
+lex!(
+    lex_number_before_identifier_starting_with_i,
+    "3 in S; 3 i",
+    vec![
+        Integer(3),
+        In,
+        Identifier("S".into()),
+        Semicolon,
+        Integer(3),
+        Identifier("i".into()),
+        Eof
+    ]
+);
+
+lex!(
+    lex_ellipsis,
+    "{ 1, ..., n }",
+    vec![
+        LeftBrace,
+        Integer(1),
+        Comma,
+        Ellipsis,
+        Comma,
+        Identifier("n".into()),
+        RightBrace,
+        Eof
+    ]
+);
+
+lex!(
+    lex_interval_ends,
+    "[0..1.5)",
+    vec![
+        LeftSquareBracket,
+        Integer(0),
+        DoubleDot,
+        Real("1.5".into()),
+        RightParen,
+        Eof
+    ]
+);
+
+lex!(
+    lex_absolute_values_in_braces,
+    "{|-2|, |x|}",
+    vec![
+        LeftBrace,
+        Bar,
+        Minus,
+        Integer(2),
+        Bar,
+        Comma,
+        Bar,
+        Identifier("x".into()),
+        Bar,
+        RightBrace,
+        Eof
+    ]
+);
+
+lex!(
+    lex_multiset_braces,
+    "{: 1, |x|:}",
+    vec![
+        LeftMultisetBrace,
+        Integer(1),
+        Comma,
+        Bar,
+        Identifier("x".into()),
+        Bar,
+        RightMultisetBrace,
+        Eof
+    ]
+);
+
+lex!(
+    lex_congruence,
+    "a ≡ b (mod 7) === (modulus)",
+    vec![
+        Identifier("a".into()),
+        Congruent,
+        Identifier("b".into()),
+        ModClause,
+        Integer(7),
+        RightParen,
+        Congruent,
+        LeftParen,
+        Identifier("modulus".into()),
+        RightParen,
+        Eof
+    ]
+);
+
+lex!(
+    lex_composition,
+    "f ∘ g << h ^ .5",
+    vec![
+        Identifier("f".into()),
+        Compose,
+        Identifier("g".into()),
+        Compose,
+        Identifier("h".into()),
+        Exponent,
+        Real(".5".into()),
+        Eof
+    ]
+);
+
+lex!(
+    lex_integer_too_large_for_i64,
+    "12345678901234567890 mod 97",
+    vec![
+        BigInteger("12345678901234567890".parse().unwrap()),
+        Mod,
+        Integer(97),
+        Eof
+    ]
+);
+
+lex!(
+    lex_mod_as_a_name,
+    "let mod = f(mod) mod 3",
+    vec![
+        Let,
+        Identifier("mod".into()),
+        Assign,
+        Identifier("f".into()),
+        LeftParen,
+        Identifier("mod".into()),
+        RightParen,
+        Mod,
+        Integer(3),
+        Eof
+    ]
+);
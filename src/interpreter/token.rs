@@ -1,15 +1,15 @@
 use std::fmt::{Display, Formatter, self};
-use std::hash::{Hash, Hasher};
-use rust_decimal::prelude::*;
+use num_bigint::BigInt;
 
 // token should capture line #, col start, and span
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Hash)]
 pub enum Token {
     Illegal(String),
     Eof,
     Identifier(String),
-    Real(f64),
+    Real(String),  // the source text, so decimal mode can read it exactly
     Integer(i64),
+    BigInteger(BigInt),  // an integer literal too large for an i64
     Plus,
     Minus,
     Exponent,
@@ -19,6 +19,8 @@ pub enum Token {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftMultisetBrace,
+    RightMultisetBrace,
     LeftSquareBracket,
     RightSquareBracket,
     Comma,
@@ -29,9 +31,15 @@ pub enum Token {
     True,
     False,
     Colon,
+    Ellipsis,
+    DoubleDot,  // between the ends of an interval, as in `[0 .. 1]`
     If,
     Else,
     Modulo,
+    Mod,
+    ModClause,  // `(mod`, which opens the modulus of a congruence
+    Congruent,
+    Compose,
     Undefined,
     Assign,
     Equals,
@@ -67,15 +75,6 @@ pub enum Token {
     NewLine,
 }
 
-impl Hash for Token {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        match self {
-            Token::Real(value) => Decimal::from_f64(*value).unwrap().hash(state),
-            rest => rest.hash(state)
-        }
-    }
-}
-
 impl Display for Token {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         use Token::*;
@@ -85,6 +84,7 @@ impl Display for Token {
             Identifier(value) => write!(f, "{}", value),
             Real(value) => write!(f, "{}", value),
             Integer(value) => write!(f, "{}", value),
+            BigInteger(value) => write!(f, "{}", value),
             Plus => write!(f, "+"),
             Minus => write!(f, "-"),
             Exponent => write!(f, "^"),
@@ -94,6 +94,8 @@ impl Display for Token {
             RightParen => write!(f, ")"),
             LeftBrace => write!(f, "{{"),
             RightBrace => write!(f, "}}"),
+            LeftMultisetBrace => write!(f, "{{:"),
+            RightMultisetBrace => write!(f, ":}}"),
             LeftSquareBracket => write!(f, "["),
             RightSquareBracket => write!(f, "]"),
             Comma => write!(f, ","),
@@ -104,9 +106,15 @@ impl Display for Token {
             True => write!(f, "true"),
             False => write!(f, "false"),
             Colon => write!(f, ":"),
+            Ellipsis => write!(f, "..."),
+            DoubleDot => write!(f, ".."),
             If => write!(f, "if"),
             Else => write!(f, "else"),
             Modulo => write!(f, "%"),
+            Mod => write!(f, "mod"),
+            ModClause => write!(f, "(mod"),
+            Congruent => write!(f, "\u{2261}"),
+            Compose => write!(f, "\u{2218}"),
             Undefined => write!(f, "undefined"),
             Assign => write!(f, "="),
             Equals => write!(f, "=="),
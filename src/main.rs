@@ -7,7 +7,7 @@ pub mod interpreter;
 pub mod repl;
 pub mod ast;
 
-use ast::environment::Environment;
+use ast::environment::{Environment, NumericMode};
 use repl::exec;
 use ast::modules::{set_builtins, math_constants_builtins};
 
@@ -22,6 +22,10 @@ struct Config {
     /// run in a repl
     #[argh(switch, short = 'i')]
     repl: bool,
+
+    /// use exact decimals instead of floating point for reals
+    #[argh(switch, short = 'd')]
+    decimal: bool,
 }
 
 #[derive(Debug)]
@@ -44,7 +48,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut config: Config = argh::from_env();
 
     // setup environment (memory, etc.)
-    let mut env = Environment::new();
+    let mut env = Environment::with_mode(if config.decimal { NumericMode::Decimal } else { NumericMode::Float });
 
     // read
     if let Some(path) = config.file {
@@ -27,7 +27,7 @@ pub fn exec(content: &str, env: &mut Environment) -> Result<String, Box<dyn Erro
             for stmt in stmts.iter() {
                 let obj = env.eval_statement(&stmt)?;
                 match (obj, stmt) {
-                    (Object::Undefined, Statement::Assignment { .. }) | (Object::Undefined, Statement::FunctionDeclaration { .. }) => {},
+                    (Object::Undefined, Statement::Assignment { .. }) | (Object::Undefined, Statement::FunctionDeclaration { .. }) | (Object::Undefined, Statement::Import { .. }) => {},
                     (o, _) => {
                         if count != 0 {
                             response.push_str("\n");
@@ -50,4 +50,9 @@ macro_rules! run {
         use slope::ast::environment::Environment;
         println!("{}", exec($code, &mut Environment::new()).unwrap());
     };
+    (decimal $code:expr) => {
+        use slope::repl::exec;
+        use slope::ast::environment::{Environment, NumericMode};
+        println!("{}", exec($code, &mut Environment::with_mode(NumericMode::Decimal)).unwrap());
+    };
 }
\ No newline at end of file